@@ -0,0 +1,99 @@
+// Benchmarks comparing lookups on a `Board` (which uses `HashSet`s and `Vec`s) against lookups on the dense `Grid`.
+// Every benchmark checks every cell of an 11x11 board with four snakes on it, which is roughly what a single flood
+// fill or A* search does.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hematite::objects::{board::Board, grid::Grid, point::Point};
+
+/// A standard 11x11 board with four snakes, some food and some hazards on it.
+const BOARD: &str = r#"{
+    "height": 11,
+    "width": 11,
+    "food": [{"x": 0, "y": 0}, {"x": 5, "y": 5}, {"x": 10, "y": 3}, {"x": 7, "y": 9}],
+    "hazards": [{"x": 0, "y": 10}, {"x": 1, "y": 10}, {"x": 2, "y": 10}, {"x": 3, "y": 10}],
+    "snakes": [
+        {
+            "id": "gs_aaaaaaaaaaaaaaaaaaaaaaaa", "health": 90, "length": 8, "head": {"x": 1, "y": 1}, "squad": null,
+            "body": [{"x": 1, "y": 1}, {"x": 1, "y": 2}, {"x": 1, "y": 3}, {"x": 1, "y": 4},
+                     {"x": 1, "y": 5}, {"x": 1, "y": 6}, {"x": 2, "y": 6}, {"x": 3, "y": 6}]
+        },
+        {
+            "id": "gs_bbbbbbbbbbbbbbbbbbbbbbbb", "health": 80, "length": 7, "head": {"x": 9, "y": 1}, "squad": null,
+            "body": [{"x": 9, "y": 1}, {"x": 9, "y": 2}, {"x": 9, "y": 3}, {"x": 9, "y": 4},
+                     {"x": 8, "y": 4}, {"x": 7, "y": 4}, {"x": 6, "y": 4}]
+        },
+        {
+            "id": "gs_cccccccccccccccccccccccc", "health": 70, "length": 6, "head": {"x": 9, "y": 9}, "squad": null,
+            "body": [{"x": 9, "y": 9}, {"x": 9, "y": 8}, {"x": 9, "y": 7}, {"x": 8, "y": 7},
+                     {"x": 7, "y": 7}, {"x": 6, "y": 7}]
+        },
+        {
+            "id": "gs_dddddddddddddddddddddddd", "health": 60, "length": 5, "head": {"x": 3, "y": 9}, "squad": null,
+            "body": [{"x": 3, "y": 9}, {"x": 4, "y": 9}, {"x": 5, "y": 9}, {"x": 5, "y": 8},
+                     {"x": 4, "y": 8}]
+        }
+    ]
+}"#;
+
+/// Every cell on the board.
+fn cells(board: &Board) -> Vec<Point> {
+    (0..board.width as i32)
+        .flat_map(|x| (0..board.height as i32).map(move |y| Point::new(x, y)))
+        .collect()
+}
+
+fn lookups(c: &mut Criterion) {
+    let board = serde_json::from_str::<Board>(BOARD).expect("The board should parse.");
+    let grid = Grid::new(&board);
+    let cells = cells(&board);
+
+    let mut group = c.benchmark_group("snake lookups");
+    group.bench_function("board", |b| {
+        b.iter(|| {
+            cells
+                .iter()
+                .filter(|point| {
+                    board
+                        .snakes
+                        .values()
+                        .any(|snake| snake.body.contains(point))
+                })
+                .count()
+        })
+    });
+    group.bench_function("grid", |b| {
+        b.iter(|| {
+            cells
+                .iter()
+                .filter(|point| grid.segment(point).is_some())
+                .count()
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("food and hazard lookups");
+    group.bench_function("board", |b| {
+        b.iter(|| {
+            cells
+                .iter()
+                .filter(|point| board.food.contains(point) || board.hazards.contains_key(point))
+                .count()
+        })
+    });
+    group.bench_function("grid", |b| {
+        b.iter(|| {
+            cells
+                .iter()
+                .filter(|point| grid.is_food(point) || grid.is_hazard(point))
+                .count()
+        })
+    });
+    group.finish();
+
+    c.bench_function("grid construction", |b| {
+        b.iter(|| Grid::new(black_box(&board)))
+    });
+}
+
+criterion_group!(benches, lookups);
+criterion_main!(benches);
@@ -0,0 +1,124 @@
+// Benchmarks for the searches. The first benchmark times a single search step: building the grid for a state, looking
+// up every snake's safe moves on it, and applying and undoing a turn, which is what minimax does at every node. The
+// others time whole searches with small, fixed budgets, so they finish well before the deadline and the numbers are
+// comparable between runs.
+
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use hematite::{
+    configuration::engine::{EngineConfig, SearchAlgorithm},
+    engine::Engine,
+    objects::{grid::Grid, moves::Move, GameState},
+};
+
+/// A standard game on an 11x11 board with four snakes, some food and some hazards on it.
+const STATE: &str = r#"{
+    "game": {
+        "id": "2d3d6c2a-0b35-4bbd-9a0a-2f1c0d6c2e5b",
+        "ruleset": {"name": "standard", "version": "v1.0.0", "settings": {
+            "foodSpawnChance": 15, "minimumFood": 1, "hazardDamagePerTurn": 14,
+            "royale": {"shrinkEveryNTurns": 25},
+            "squad": {"allowBodyCollisions": false, "sharedElimination": false,
+                      "sharedHealth": false, "sharedLength": false}
+        }},
+        "timeout": 500,
+        "source": "custom"
+    },
+    "turn": 40,
+    "board": {
+        "height": 11,
+        "width": 11,
+        "food": [{"x": 0, "y": 0}, {"x": 5, "y": 5}, {"x": 10, "y": 3}, {"x": 7, "y": 9}],
+        "hazards": [{"x": 0, "y": 10}, {"x": 1, "y": 10}, {"x": 2, "y": 10}, {"x": 3, "y": 10}],
+        "snakes": [
+            {
+                "id": "gs_aaaaaaaaaaaaaaaaaaaaaaaa", "name": "a", "health": 90, "length": 8, "head": {"x": 1, "y": 1},
+                "squad": "",
+                "body": [{"x": 1, "y": 1}, {"x": 1, "y": 2}, {"x": 1, "y": 3}, {"x": 1, "y": 4},
+                         {"x": 1, "y": 5}, {"x": 1, "y": 6}, {"x": 2, "y": 6}, {"x": 3, "y": 6}]
+            },
+            {
+                "id": "gs_bbbbbbbbbbbbbbbbbbbbbbbb", "name": "b", "health": 80, "length": 7, "head": {"x": 9, "y": 1},
+                "squad": "",
+                "body": [{"x": 9, "y": 1}, {"x": 9, "y": 2}, {"x": 9, "y": 3}, {"x": 9, "y": 4},
+                         {"x": 8, "y": 4}, {"x": 7, "y": 4}, {"x": 6, "y": 4}]
+            },
+            {
+                "id": "gs_cccccccccccccccccccccccc", "name": "c", "health": 70, "length": 6, "head": {"x": 9, "y": 9},
+                "squad": "",
+                "body": [{"x": 9, "y": 9}, {"x": 9, "y": 8}, {"x": 9, "y": 7}, {"x": 8, "y": 7},
+                         {"x": 7, "y": 7}, {"x": 6, "y": 7}]
+            },
+            {
+                "id": "gs_dddddddddddddddddddddddd", "name": "d", "health": 60, "length": 5, "head": {"x": 3, "y": 9},
+                "squad": "",
+                "body": [{"x": 3, "y": 9}, {"x": 4, "y": 9}, {"x": 5, "y": 9}, {"x": 5, "y": 8},
+                         {"x": 4, "y": 8}]
+            }
+        ]
+    },
+    "you": {
+        "id": "gs_aaaaaaaaaaaaaaaaaaaaaaaa", "name": "a", "health": 90, "length": 8, "head": {"x": 1, "y": 1},
+        "squad": "",
+        "body": [{"x": 1, "y": 1}, {"x": 1, "y": 2}, {"x": 1, "y": 3}, {"x": 1, "y": 4},
+                 {"x": 1, "y": 5}, {"x": 1, "y": 6}, {"x": 2, "y": 6}, {"x": 3, "y": 6}]
+    }
+}"#;
+
+fn step(c: &mut Criterion) {
+    let mut state = serde_json::from_str::<GameState>(STATE).expect("The state should parse.");
+
+    c.bench_function("search step", |b| {
+        b.iter(|| {
+            let grid = Grid::new(black_box(&state.board));
+            let moves = grid
+                .snakes()
+                .iter()
+                .enumerate()
+                .map(|(slot, snake)| {
+                    let safe = grid.safe_moves(slot);
+                    (snake.id, safe.first().copied().unwrap_or(Move::Up))
+                })
+                .collect::<HashMap<_, _>>();
+
+            let delta = state.apply(&moves);
+            state.undo(delta);
+        })
+    });
+}
+
+fn searches(c: &mut Criterion) {
+    let state = serde_json::from_str::<GameState>(STATE).expect("The state should parse.");
+
+    let mut config = EngineConfig::default();
+    config.mcts.iterations = 50;
+    config.mcts.max_depth = 20;
+    config.minimax.depth = 2;
+
+    let mut group = c.benchmark_group("search");
+    group.sample_size(10);
+    for (name, search) in [
+        ("mcts", SearchAlgorithm::Mcts),
+        ("duct", SearchAlgorithm::Duct),
+        ("minimax", SearchAlgorithm::Minimax),
+    ] {
+        let config = EngineConfig {
+            search,
+            ..config.clone()
+        };
+
+        // The deadline is counted from when the engine is created, so every search gets a fresh one.
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || Engine::new(config.clone(), state.clone()),
+                |engine| engine.searching_move(),
+                BatchSize::PerIteration,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, step, searches);
+criterion_main!(benches);
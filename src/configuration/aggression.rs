@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for when the snake goes on the attack.
+#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+pub struct AggressionConfig {
+    /// How much longer than every enemy the snake has to be before it hunts them. Range: 1 and up. Enemies that
+    /// are only one shorter can grow to our length by eating once, so anything less than 2 is risky.
+    #[serde(default = "AggressionConfig::default_length_advantage")]
+    pub length_advantage: u32,
+}
+
+impl Default for AggressionConfig {
+    fn default() -> Self {
+        Self {
+            length_advantage: Self::default_length_advantage(),
+        }
+    }
+}
+
+impl AggressionConfig {
+    /// Returns the default length advantage.
+    fn default_length_advantage() -> u32 {
+        2
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for how much moving into a cell costs when pathfinding with A*. Paths with lower total costs
+/// are preferred.
+#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+pub struct CostConfig {
+    /// The cost of moving into food. Range: 0 to `normal`. This should stay at or below `normal`, so that
+    /// food is never avoided; it can't be negative, or paths would loop between neighboring foods forever.
+    #[serde(default = "CostConfig::default_food")]
+    pub food: u32,
+
+    /// The cost of moving next to an enemy's head, where it might meet us head-to-head. Range: 0 and up,
+    /// usually a few times `normal`.
+    #[serde(default = "CostConfig::default_near_head")]
+    pub near_head: u32,
+
+    /// The cost of moving into any other cell. Range: 1 and up. Every move costs a point of health, so this
+    /// is the unit every other cost is measured in.
+    #[serde(default = "CostConfig::default_normal")]
+    pub normal: u32,
+
+    /// The extra cost of every point of damage a hazard deals, on top of `normal`. Range: 0 and up. At 0,
+    /// hazards are only avoided when they would kill us.
+    #[serde(default = "CostConfig::default_hazard")]
+    pub hazard: u32,
+
+    /// The cost of making our first move into the last way out an ally has left. Range: 0 and up. It's only
+    /// a cost, and not a rule, so that we can still take the cell when every other path is worse.
+    #[serde(default = "CostConfig::default_ally_exit")]
+    pub ally_exit: u32,
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        Self {
+            food: Self::default_food(),
+            near_head: Self::default_near_head(),
+            normal: Self::default_normal(),
+            hazard: Self::default_hazard(),
+            ally_exit: Self::default_ally_exit(),
+        }
+    }
+}
+
+impl CostConfig {
+    /// Returns the default food cost.
+    fn default_food() -> u32 {
+        0
+    }
+
+    /// Returns the default cost of moving next to an enemy's head.
+    fn default_near_head() -> u32 {
+        3
+    }
+
+    /// Returns the default cost of a normal move.
+    fn default_normal() -> u32 {
+        1
+    }
+
+    /// Returns the default cost of a point of hazard damage.
+    fn default_hazard() -> u32 {
+        1
+    }
+
+    /// Returns the default cost of taking an ally's last way out.
+    fn default_ally_exit() -> u32 {
+        10
+    }
+}
@@ -1,11 +1,47 @@
+use super::{
+    aggression::AggressionConfig, costs::CostConfig, evaluation::EvaluationConfig,
+    hunger::HungerConfig, mcts::MCTSConfig, minimax::MinimaxConfig, timing::TimingConfig,
+};
 use serde::{Deserialize, Serialize};
 
 /// The structure holding the configuration for the engine.
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EngineConfig {
     /// How many moves to be hungry for (i.e., the number of initial moves the snake is hungry for).
     #[serde(default = "EngineConfig::default_hungry_moves")]
     pub hungry_moves: u32,
+
+    /// The algorithm to search for moves with when the snake isn't hungry.
+    #[serde(default)]
+    pub search: SearchAlgorithm,
+
+    /// The configuration for the MCTS search. Also used by DUCT.
+    #[serde(default)]
+    pub mcts: MCTSConfig,
+
+    /// The configuration for the minimax search.
+    #[serde(default)]
+    pub minimax: MinimaxConfig,
+
+    /// The configuration for how much time to spend on each move.
+    #[serde(default)]
+    pub timing: TimingConfig,
+
+    /// The weights searches evaluate unfinished games with.
+    #[serde(default)]
+    pub evaluation: EvaluationConfig,
+
+    /// When the snake gets hungry.
+    #[serde(default)]
+    pub hunger: HungerConfig,
+
+    /// The costs of moving into different cells when pathfinding.
+    #[serde(default)]
+    pub costs: CostConfig,
+
+    /// When the snake goes on the attack.
+    #[serde(default)]
+    pub aggression: AggressionConfig,
 }
 
 impl EngineConfig {
@@ -19,6 +55,29 @@ impl Default for EngineConfig {
     fn default() -> Self {
         Self {
             hungry_moves: Self::default_hungry_moves(),
+            search: SearchAlgorithm::default(),
+            mcts: MCTSConfig::default(),
+            minimax: MinimaxConfig::default(),
+            timing: TimingConfig::default(),
+            evaluation: EvaluationConfig::default(),
+            hunger: HungerConfig::default(),
+            costs: CostConfig::default(),
+            aggression: AggressionConfig::default(),
         }
     }
 }
+
+/// The algorithms the engine can search for moves with.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchAlgorithm {
+    /// Monte Carlo Tree Search over our own moves, with the other snakes moving randomly.
+    #[default]
+    Mcts,
+
+    /// Decoupled UCT, which searches over the moves of every snake at the same time.
+    Duct,
+
+    /// Depth-limited minimax with alpha-beta pruning, which assumes every other snake is out to get us.
+    Minimax,
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for how states are evaluated during searches. Every field is the weight of one feature of a
+/// state; the value of a state is the weighted sum of its features.
+#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationConfig {
+    /// The weight of how much more of the board we control than the enemy that controls the most, as a
+    /// fraction of the board (so the feature is between -1 and 1).
+    #[serde(default = "EvaluationConfig::default_territory")]
+    pub territory: f64,
+
+    /// The weight of how much longer we are than the longest enemy.
+    #[serde(default = "EvaluationConfig::default_length")]
+    pub length: f64,
+
+    /// The weight of our health, as a fraction of full health (so the feature is between 0 and 1).
+    #[serde(default = "EvaluationConfig::default_health")]
+    pub health: f64,
+
+    /// The weight of how close the nearest food we get to before every enemy is. The feature is
+    /// `1 / (1 + distance)`, half that for food we tie for with an enemy as long as us, or 0 if there is no
+    /// such food.
+    #[serde(default = "EvaluationConfig::default_food")]
+    pub food: f64,
+
+    /// The weight of being able to reach our own tail (the feature is either 0 or 1).
+    #[serde(default = "EvaluationConfig::default_tail")]
+    pub tail: f64,
+}
+
+impl Default for EvaluationConfig {
+    fn default() -> Self {
+        Self {
+            territory: Self::default_territory(),
+            length: Self::default_length(),
+            health: Self::default_health(),
+            food: Self::default_food(),
+            tail: Self::default_tail(),
+        }
+    }
+}
+
+impl EvaluationConfig {
+    /// Returns the default territory weight.
+    fn default_territory() -> f64 {
+        10.0
+    }
+
+    /// Returns the default length weight.
+    fn default_length() -> f64 {
+        0.5
+    }
+
+    /// Returns the default health weight.
+    fn default_health() -> f64 {
+        1.0
+    }
+
+    /// Returns the default food weight.
+    fn default_food() -> f64 {
+        2.0
+    }
+
+    /// Returns the default tail weight.
+    fn default_tail() -> f64 {
+        2.0
+    }
+}
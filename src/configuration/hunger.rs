@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for when the snake gets hungry, and how picky it is about food.
+#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+pub struct HungerConfig {
+    /// Below this much health, the snake is hungry. Range: 0 to 100, where 0 never gets hungry because of
+    /// health, and 100 is always hungry.
+    #[serde(default = "HungerConfig::default_threshold")]
+    pub threshold: i32,
+
+    /// If the snake would arrive at the food it's going for with less than this much health, it gets hungry
+    /// early. Range: 0 to 100, usually much lower than `threshold`.
+    #[serde(default = "HungerConfig::default_margin")]
+    pub margin: i32,
+
+    /// Below this much health, the snake goes for food it might not get to first. Range: 0 to 100, usually
+    /// lower than `threshold`.
+    #[serde(default = "HungerConfig::default_desperate")]
+    pub desperate: i32,
+}
+
+impl Default for HungerConfig {
+    fn default() -> Self {
+        Self {
+            threshold: Self::default_threshold(),
+            margin: Self::default_margin(),
+            desperate: Self::default_desperate(),
+        }
+    }
+}
+
+impl HungerConfig {
+    /// Returns the default hunger threshold.
+    fn default_threshold() -> i32 {
+        50
+    }
+
+    /// Returns the default hunger margin.
+    fn default_margin() -> i32 {
+        10
+    }
+
+    /// Returns the default desperate health.
+    fn default_desperate() -> i32 {
+        25
+    }
+}
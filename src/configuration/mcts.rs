@@ -7,9 +7,18 @@ pub struct MCTSConfig {
     #[serde(default = "MCTSConfig::default_games_per_search")]
     pub games_per_search: u32,
 
-    /// The number of iterations to run the MCTS algorithm for.
+    /// The maximum number of turns to simulate ahead of the current turn.
     #[serde(default = "MCTSConfig::default_max_depth")]
     pub max_depth: u32,
+
+    /// The maximum number of iterations to run the MCTS algorithm for. The search stops early if it runs
+    /// out of time.
+    #[serde(default = "MCTSConfig::default_iterations")]
+    pub iterations: u32,
+
+    /// How much the search prefers exploring less-visited moves over exploiting the best ones.
+    #[serde(default = "MCTSConfig::default_exploration")]
+    pub exploration: f64,
 }
 
 impl Default for MCTSConfig {
@@ -17,18 +26,30 @@ impl Default for MCTSConfig {
         Self {
             games_per_search: Self::default_games_per_search(),
             max_depth: Self::default_max_depth(),
+            iterations: Self::default_iterations(),
+            exploration: Self::default_exploration(),
         }
     }
 }
 
 impl MCTSConfig {
-    /// Returns the default number of iterations.
+    /// Returns the default number of games per search iteration.
     fn default_games_per_search() -> u32 {
         5
     }
 
-    /// Returns the default number of iterations.
+    /// Returns the default maximum depth.
     fn default_max_depth() -> u32 {
         100
     }
+
+    /// Returns the default number of iterations.
+    fn default_iterations() -> u32 {
+        10_000
+    }
+
+    /// Returns the default exploration constant. This is the usual `sqrt(2)` from UCT.
+    fn default_exploration() -> f64 {
+        std::f64::consts::SQRT_2
+    }
 }
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for minimax
+#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+pub struct MinimaxConfig {
+    /// The maximum number of turns to search ahead of the current turn. The search deepens one turn at
+    /// a time until it either runs out of time or reaches this depth.
+    #[serde(default = "MinimaxConfig::default_depth")]
+    pub depth: u32,
+
+    /// The size of the transposition table, in megabytes.
+    #[serde(default = "MinimaxConfig::default_table_size")]
+    pub table_size: u32,
+}
+
+impl Default for MinimaxConfig {
+    fn default() -> Self {
+        Self {
+            depth: Self::default_depth(),
+            table_size: Self::default_table_size(),
+        }
+    }
+}
+
+impl MinimaxConfig {
+    /// Returns the default depth.
+    fn default_depth() -> u32 {
+        16
+    }
+
+    /// Returns the default transposition table size.
+    fn default_table_size() -> u32 {
+        16
+    }
+}
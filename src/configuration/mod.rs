@@ -1,10 +1,18 @@
+pub mod aggression;
+pub mod costs;
 pub mod engine;
+pub mod evaluation;
+pub mod hunger;
 pub mod mcts;
+pub mod minimax;
 pub mod snake;
+pub mod timing;
 
 use self::{engine::EngineConfig, snake::SnakeConfig};
+use crate::objects::settings::GameType;
 use cargo_toml::Manifest;
 use serde::{Deserialize, Serialize};
+use toml::Value;
 
 /// The structure holding the configuration for the battlesnake itself and the engine.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -15,25 +23,114 @@ pub struct Config {
     /// The configuration for the battlesnake itself.
     pub snake: SnakeConfig,
 
-    /// The configuration for the engine.
+    /// The base configuration for the engine, used for every game that doesn't have a profile.
     #[serde(default)]
     pub engine: EngineConfig,
+
+    /// The engine configuration exactly as it was written, including the profiles for game types
+    /// (`[package.metadata.engine.<game_type>]`) and maps (`[package.metadata.engine.maps.<map>]`).
+    #[serde(skip)]
+    profiles: Option<Value>,
 }
 
 impl Config {
     /// Loads the configuration from `Cargo.toml`. Panics if either the `package` or
-    /// `package.metadata` keys are missing from the configuration.
+    /// `package.metadata` keys are missing from the configuration, or if any of the profiles are invalid.
     pub fn load() -> Self {
         // The cargo.toml file is included as a byte array at compile time.
         let cargo_file = include_bytes!("../../Cargo.toml");
 
         // This is the complete structure of the cargo.toml file.
-        let manifest = toml::from_slice::<Manifest<Config>>(cargo_file).unwrap();
+        let manifest = toml::from_slice::<Manifest<Value>>(cargo_file).unwrap();
 
-        manifest
+        let metadata = manifest
             .package
             .expect("The 'package' key should exist")
             .metadata
-            .expect("The 'package.metadata' key should exist")
+            .expect("The 'package.metadata' key should exist");
+
+        let mut config = metadata.clone().try_into::<Config>().unwrap();
+        config.profiles = metadata.get("engine").cloned();
+
+        // Make sure every profile is valid now, instead of when a game using it starts.
+        for game_type in GameType::ALL {
+            config.engine_for(game_type, None);
+        }
+
+        if let Some(maps) = config.profile(&["maps"]).and_then(Value::as_table) {
+            for map in maps.keys() {
+                config.engine_for(GameType::Standard, Some(map));
+            }
+        }
+
+        config
+    }
+
+    /// Returns the engine configuration for a game of the given type on the given map. This is the base
+    /// engine configuration, overlaid with the game type's profile, and then with the map's profile.
+    pub fn engine_for(&self, game_type: GameType, map: Option<&str>) -> EngineConfig {
+        let Some(base) = &self.profiles else {
+            return self.engine.clone();
+        };
+
+        let mut merged = base.clone();
+
+        if let Some(profile) = self.profile(&[game_type.name()]) {
+            overlay(&mut merged, profile);
+        }
+
+        if let Some(profile) = map.and_then(|map| self.profile(&["maps", map])) {
+            overlay(&mut merged, profile);
+        }
+
+        merged.try_into().unwrap_or_else(|error| {
+            panic!("The engine profile for {game_type:?} on {map:?} should be valid: {error}")
+        })
+    }
+}
+
+/// Private API for `Config`.
+impl Config {
+    /// Gets the profile at the given path under `package.metadata.engine`, if it exists.
+    fn profile(&self, path: &[&str]) -> Option<&Value> {
+        path.iter()
+            .try_fold(self.profiles.as_ref()?, |value, key| value.get(key))
+    }
+}
+
+/// Overlays `profile` on top of `base`: every key in `profile` replaces the same key in `base`, except for
+/// tables, which are overlaid key by key.
+fn overlay(base: &mut Value, profile: &Value) {
+    match (base, profile) {
+        (Value::Table(base), Value::Table(profile)) => {
+            for (key, value) in profile {
+                match base.get_mut(key) {
+                    Some(existing) => overlay(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, profile) => *base = profile.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_type_profiles_overlay_the_base_engine() {
+        let config = Config::load();
+        let standard = config.engine_for(GameType::Standard, None);
+        let royale = config.engine_for(GameType::Royale, None);
+
+        assert_eq!(standard.costs.hazard, 1);
+        assert_eq!(standard.hunger.threshold, 50);
+        assert_eq!(royale.costs.hazard, 2);
+        assert_eq!(royale.hunger.threshold, 60);
+        assert_eq!(royale.costs.near_head, standard.costs.near_head);
+        assert_eq!(royale.hunger.margin, standard.hunger.margin);
     }
 }
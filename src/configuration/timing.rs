@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for how much time the engine spends on each move.
+#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+pub struct TimingConfig {
+    /// How many milliseconds of the game's timeout to leave for the response to get back to the game
+    /// server.
+    #[serde(default = "TimingConfig::default_network_margin")]
+    pub network_margin: u32,
+
+    /// OPTIONAL: The most milliseconds to spend on any move, even if the game's timeout allows more.
+    pub turn_budget: Option<u32>,
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        Self {
+            network_margin: Self::default_network_margin(),
+            turn_budget: None,
+        }
+    }
+}
+
+impl TimingConfig {
+    /// Returns the default network margin.
+    fn default_network_margin() -> u32 {
+        150
+    }
+}
@@ -0,0 +1,120 @@
+// Evaluators tell searches how good a game that isn't over yet looks for a snake. Searches can only look so far
+// ahead, so when they stop, they need some idea of who is winning. The default evaluator looks at a handful of
+// features of the state, and weighs them with the weights from the engine configuration.
+
+use crate::{
+    configuration::evaluation::EvaluationConfig,
+    engine::utils::area::Territory,
+    objects::{contest::Contest, grid::Grid, settings::GameType, snake::SnakeID, GameState},
+};
+
+/// A way of evaluating game states. Evaluators are shared between search threads, so they have to be `Send` and
+/// `Sync`.
+pub trait Evaluator: Send + Sync {
+    /// Returns how good the state (whose grid is `grid`) is for the snake with the given ID. Higher values are
+    /// better. The value only needs to make sense compared to other values from the same evaluator.
+    fn evaluate(&self, state: &GameState, grid: &Grid, you: &SnakeID) -> f64;
+}
+
+impl<F> Evaluator for F
+where
+    F: Fn(&GameState, &Grid, &SnakeID) -> f64 + Send + Sync,
+{
+    fn evaluate(&self, state: &GameState, grid: &Grid, you: &SnakeID) -> f64 {
+        self(state, grid, you)
+    }
+}
+
+/// The default evaluator: a weighted sum of territory, length difference, health, distance to the nearest food
+/// we'd win (or tie for), and whether we can reach our own tail. See `EvaluationConfig` for the details of each feature.
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedEvaluator {
+    /// The weight of every feature.
+    weights: EvaluationConfig,
+}
+
+impl WeightedEvaluator {
+    /// Creates a new evaluator with the given weights.
+    pub fn new(weights: EvaluationConfig) -> Self {
+        Self { weights }
+    }
+}
+
+impl Default for WeightedEvaluator {
+    fn default() -> Self {
+        Self::new(EvaluationConfig::default())
+    }
+}
+
+impl Evaluator for WeightedEvaluator {
+    fn evaluate(&self, state: &GameState, grid: &Grid, you: &SnakeID) -> f64 {
+        let Some(slot) = grid.slot(you) else {
+            return 0.0;
+        };
+
+        let snakes = grid.snakes();
+        let snake = &snakes[slot];
+        let length = snake.body.len();
+
+        // Territory, as a fraction of the board.
+        let heads = snakes.iter().map(|snake| snake.head()).collect::<Vec<_>>();
+        let territory = Territory::new(grid, &heads, |_, _, _| true);
+        let enemy_territory = snakes
+            .iter()
+            .filter(|other| other.id != *you)
+            .map(|other| territory.count(&other.id))
+            .max()
+            .unwrap_or(0);
+        let territory = (territory.count(you) as f64 - enemy_territory as f64)
+            / (grid.width * grid.height) as f64;
+
+        // Length difference to the longest enemy.
+        let longest = snakes
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != slot)
+            .map(|(_, snake)| snake.body.len())
+            .max()
+            .unwrap_or(0);
+        let length_difference = length as f64 - longest as f64;
+
+        // Health, as a fraction of full health.
+        let health = snake.health as f64 / 100.0;
+
+        // Closeness of the nearest food we get to first. Food we tie for is only worth half as much, and our
+        // allies don't contest food with us.
+        let you_snake = &state.board.snakes[you];
+        let is_squad = state.game.ruleset.name == GameType::Squad;
+        let food = grid
+            .food_contests(slot, state.board.food.iter().copied(), |id| {
+                !is_squad || !state.board.snakes[id].is_ally_of(you_snake)
+            })
+            .into_iter()
+            .filter_map(|food| {
+                let closeness = 1.0 / (1.0 + food.distance? as f64);
+
+                match food.contest {
+                    Contest::Win => Some(closeness),
+                    Contest::Tie => Some(closeness / 2.0),
+                    Contest::Lose => None,
+                }
+            })
+            .fold(0.0, f64::max);
+
+        // Whether we can follow our own tail.
+        let tail = if grid
+            .distances(slot, &snake.head(), 0)
+            .contains_key(&snake.tail())
+        {
+            1.0
+        } else {
+            0.0
+        };
+
+        self.weights.territory * territory
+            + self.weights.length * length_difference
+            + self.weights.health * health
+            + self.weights.food * food
+            + self.weights.tail * tail
+    }
+}
@@ -1,11 +1,24 @@
+pub mod evaluation;
 mod modes;
 mod rules;
+pub mod scoring;
+mod search;
+pub mod strategy;
 mod utils;
 
-use self::modes::Mode;
+use self::{
+    evaluation::{Evaluator, WeightedEvaluator},
+    rules::royale::ShrinkForecast,
+    strategy::{Searching, Strategy, StrategyRegistry},
+    utils::{area::Reach, rcsp::FoodPath},
+};
 use crate::{
     configuration::engine::EngineConfig,
-    objects::{moves::Move, point::Point, snake::Snake, GameState},
+    objects::{grid::Grid, moves::Move, point::Point, GameState},
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
 /// The engine for Hematite.
@@ -17,41 +30,135 @@ pub struct Engine {
     /// The current state of the game.
     state: GameState,
 
-    /// The current mode of the engine.
-    mode: Mode,
+    /// A dense grid of the current board, for fast lookups.
+    grid: Grid,
+
+    /// A forecast of where hazards will be in upcoming turns, in royale games.
+    forecast: Option<ShrinkForecast>,
+
+    /// How much of the board we can get to after every move, in the order of `Move::all()`. This is worked out
+    /// as soon as the state is received, so checking the move we picked doesn't eat into the network margin
+    /// once the search is done.
+    reaches: [Reach; 4],
+
+    /// The food we want to go for this turn, if any (see `food_target`). Picking it takes food contests and a
+    /// constrained search, so it is only worked out once, when the state is received.
+    food: Option<FoodPath>,
+
+    /// The strategies the engine picks from every turn.
+    strategies: StrategyRegistry,
+
+    /// The strategy the engine is currently using.
+    strategy: Arc<dyn Strategy>,
+
+    /// How searches judge games that haven't finished by the time they stop looking ahead.
+    evaluator: Arc<dyn Evaluator>,
+
+    /// The moves the strategy scored on the last call to `get_move`, from best to worst.
+    proposals: Vec<(Move, f64)>,
+
+    /// The move the last call to `get_move` made.
+    chosen: Option<Move>,
+
+    /// When the current game state was received.
+    received: Instant,
 }
 
 /// Public API for the engine.
 impl Engine {
-    /// Create a new engine.
+    /// Create a new engine, with the built-in strategies.
     pub fn new(config: EngineConfig, initial_state: GameState) -> Self {
-        Self {
+        Self::with_strategies(config, initial_state, StrategyRegistry::default())
+    }
+
+    /// Create a new engine that picks from the given strategies.
+    pub fn with_strategies(
+        config: EngineConfig,
+        initial_state: GameState,
+        strategies: StrategyRegistry,
+    ) -> Self {
+        let grid = Grid::new(&initial_state.board);
+        let evaluator = Arc::new(WeightedEvaluator::new(config.evaluation));
+
+        let mut engine = Self {
             config,
+            forecast: ShrinkForecast::new(&initial_state, &grid),
+            reaches: [Reach::default(); 4],
+            food: None,
+            grid,
             state: initial_state.clone(),
-            mode: Mode::Hungry,
-        }
+            strategies,
+            strategy: Arc::new(Searching),
+            evaluator,
+            proposals: Vec::new(),
+            chosen: None,
+            received: Instant::now(),
+        };
+
+        engine.reaches = engine.measure_reaches();
+        engine.food = engine.find_food_target();
+        engine
+    }
+
+    /// Replaces the evaluator searches judge unfinished games with. By default, this is a `WeightedEvaluator`
+    /// with the weights from the engine configuration.
+    pub fn with_evaluator(mut self, evaluator: Arc<dyn Evaluator>) -> Self {
+        self.evaluator = evaluator;
+        self
     }
 
     /// Update the engine with a new game state.
     pub fn update(&mut self, state: GameState) {
+        self.received = Instant::now();
+        self.grid = Grid::new(&state.board);
+        self.forecast = ShrinkForecast::new(&state, &self.grid);
         self.state = state.clone();
+        self.reaches = self.measure_reaches();
+        self.food = self.find_food_target();
+        // The last turn's proposals don't apply to the new state.
+        self.proposals.clear();
+        self.chosen = None;
         // Update the mode of the engine.
         self.update_mode();
     }
 
+    /// Get the current state of the game, as the engine sees it.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
     /// Get the next move for the snake. Should always be called before `update`, to
     /// update both the game state as well as the mode the engine is in.
     pub fn get_move(&mut self) -> Move {
-        // Get the move the engine makes based on the mode it's in.
-        let move_ = match self.mode {
-            Mode::Hungry => self.hungry_move(),
-            // Mode::Searching => self.searching_move(),
-        };
+        // Get the moves the strategy the engine is using would make, best first. Ties keep the strategy's order.
+        let mut proposals = self.strategy.clone().score_moves(self);
+        proposals.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        self.proposals = proposals;
 
-        match move_ {
-            Some(m) => m,
+        // Make the best proposal that doesn't trap us. If none of them are free of traps, let trap avoidance
+        // pick a move instead of the best one. If the strategy couldn't come up with a move, try to survive, and
+        // if that fails too, trap avoidance picks the best of our safe moves.
+        let proposed = self
+            .proposals
+            .iter()
+            .map(|(move_, _)| *move_)
+            .find(|move_| self.is_trap_free(*move_))
+            .or_else(|| self.proposals.first().map(|(move_, _)| *move_))
+            .or_else(|| self.survival_move())
+            .or_else(|| self.safe_moves().next());
+
+        let move_ = match proposed {
+            Some(m) => self.avoid_traps(m),
             None => self.random_move(),
-        }
+        };
+
+        self.chosen = Some(move_);
+        move_
+    }
+
+    /// Get the strategy the engine is currently using.
+    pub fn strategy(&self) -> &dyn Strategy {
+        self.strategy.as_ref()
     }
 }
 
@@ -77,18 +184,69 @@ impl Engine {
         self.state.turn
     }
 
-    /// Get all the food on the board. Returns an iterator over the food.
-    fn food(&self) -> impl Iterator<Item = &Point> + '_ {
-        self.state.board.food.iter()
+    /// Get the time by which the engine has to have picked a move. This is the game's timeout minus the
+    /// network margin, or the turn budget if that is shorter, counted from when the state was received. Anything
+    /// that has to happen after the search (like checking the move for traps) is worked out before the search
+    /// starts, so the search can use all of this.
+    fn deadline(&self) -> Instant {
+        let timing = self.config.timing;
+        let mut budget = self
+            .state
+            .game
+            .timeout
+            .saturating_sub(timing.network_margin);
+
+        if let Some(turn_budget) = timing.turn_budget {
+            budget = budget.min(turn_budget);
+        }
+
+        self.received + Duration::from_millis(budget as u64)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{configuration::timing::TimingConfig, objects::testing::TestState};
+
+    /// An engine for a game with a 500ms timeout, using the given timing.
+    fn timed_engine(network_margin: u32, turn_budget: Option<u32>) -> Engine {
+        let config = EngineConfig {
+            timing: TimingConfig {
+                network_margin,
+                turn_budget,
+            },
+            ..EngineConfig::default()
+        };
+        let state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .build();
+
+        Engine::new(config, state)
+    }
+
+    #[test]
+    fn the_deadline_leaves_the_network_margin() {
+        let engine = timed_engine(100, None);
+        assert_eq!(
+            engine.deadline(),
+            engine.received + Duration::from_millis(400)
+        );
 
-    /// Get all the hazards on the board.
-    fn hazards(&self) -> impl Iterator<Item = &Point> + '_ {
-        self.state.board.hazards.iter()
+        // A turn budget that is longer than what the timeout leaves doesn't change anything.
+        let engine = timed_engine(100, Some(450));
+        assert_eq!(
+            engine.deadline(),
+            engine.received + Duration::from_millis(400)
+        );
     }
 
-    /// Get all the snakes on the board. Returns an iterator over the snakes.
-    fn snakes(&self) -> impl Iterator<Item = &Snake> + '_ {
-        self.state.board.snakes.values()
+    #[test]
+    fn the_deadline_keeps_to_a_shorter_turn_budget() {
+        let engine = timed_engine(100, Some(250));
+        assert_eq!(
+            engine.deadline(),
+            engine.received + Duration::from_millis(250)
+        );
     }
 }
@@ -0,0 +1,213 @@
+// When we're comfortably longer than everyone else, we can afford to go after them instead of just eating. There are
+// two ways to kill a snake: cut it off, so that the space it has left can't hold it, or meet it head-to-head when
+// it's shorter than us. This mode looks for moves that do either.
+
+use crate::{
+    engine::{utils::area::Territory, Engine},
+    objects::{moves::Move, point::Point, snake::Snake},
+};
+use std::{
+    cmp::Reverse,
+    collections::{HashSet, VecDeque},
+};
+
+/// Engine API for aggressive moves.
+impl Engine {
+    /// Returns the move the snake should make when it's hunting. Moves that cut an enemy off come first, then
+    /// moves that force a shorter enemy into a head-to-head, then moves that threaten one. Ties go to the move
+    /// that leaves our allies a way out, then to the one that takes the most territory from our enemies, and then
+    /// to the one that leaves them with the least space. Returns None if no move attacks anyone.
+    pub fn aggressive_move(&self) -> Option<Move> {
+        let territory = self.territory();
+        let (best, attack) = self
+            .safe_neighbors(self.head())
+            .map(|point| (point, self.attack(&point, &territory)))
+            .max_by_key(|(_, attack)| *attack)?;
+
+        if attack.trapped == 0 && attack.forced == 0 && attack.threatened == 0 {
+            return None;
+        }
+
+        self.move_between(self.head(), &best)
+    }
+
+    /// Returns true if we should go on the attack: we are at least the configured length advantage longer than
+    /// every enemy. Whether we have enough health to hunt is up to the hungry strategy, which comes first.
+    pub fn is_aggressive(&self) -> bool {
+        let advantage = self.config.aggression.length_advantage;
+
+        self.enemy_snakes()
+            .map(|snake| snake.length)
+            .max()
+            .is_some_and(|longest| self.len() >= longest + advantage)
+    }
+}
+
+/// How much a move would hurt our enemies. Fields are in order of importance, so attacks can be compared
+/// directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Attack {
+    /// How many enemies the move would leave with less space than they need to fit in, that have enough
+    /// space now.
+    trapped: usize,
+
+    /// How many shorter enemies would have no move other than into our head.
+    forced: usize,
+
+    /// How many shorter enemies could move into our head.
+    threatened: usize,
+
+    /// Whether the move leaves every ally a way out.
+    spares_allies: bool,
+
+    /// How many cells our enemies control now that we would control after the move.
+    taken: usize,
+
+    /// How little space our enemies would have left, in total.
+    space: Reverse<u32>,
+}
+
+/// Private API for aggressive moves.
+impl Engine {
+    /// Works out how much moving our head to `point` would hurt our enemies, who control `territory` now.
+    fn attack(&self, point: &Point, territory: &Territory) -> Attack {
+        let you = self.state.you.id;
+        let mut attack = Attack {
+            trapped: 0,
+            forced: 0,
+            threatened: 0,
+            spares_allies: !self.blocks_ally(point),
+            taken: self
+                .territory_with(point)
+                .owned(&you)
+                .filter(|cell| {
+                    territory.owner(cell).is_some_and(|owner| {
+                        owner != you
+                            && self
+                                .state
+                                .board
+                                .snakes
+                                .get(&owner)
+                                .is_some_and(|snake| !self.is_ally(snake))
+                    })
+                })
+                .count(),
+            space: Reverse(0),
+        };
+
+        for enemy in self.enemy_snakes() {
+            let space = self.space_left(enemy, Some(point));
+            attack.space.0 += space;
+
+            // Enemies that are already trapped are trapped whatever we do, so only count the ones we trap.
+            if space < enemy.length && self.space_left(enemy, None) >= enemy.length {
+                attack.trapped += 1;
+            }
+
+            if enemy.length >= self.len() || !self.neighbors(&enemy.head).contains(point) {
+                continue;
+            }
+
+            attack.threatened += 1;
+
+            // If the only way out the enemy has is into our head, it has to meet us head-to-head (and lose).
+            if self
+                .neighbors(&enemy.head)
+                .iter()
+                .all(|exit| exit == point || self.vacates_for(enemy, exit) > 1)
+            {
+                attack.forced += 1;
+            }
+        }
+
+        attack
+    }
+
+    /// Returns how many cells `enemy` could get to if our head were at `blocked`, or if we stayed out of its way
+    /// when it's None. Like `area_accessible`, bodies can be moved through once they've moved out of the way (or
+    /// right away, for the enemy's squadmates). Our new head stays where it is for long enough that it counts as a
+    /// wall.
+    fn space_left(&self, enemy: &Snake, blocked: Option<&Point>) -> u32 {
+        let mut visited = HashSet::from([enemy.head]);
+        visited.extend(blocked);
+        let mut queue = VecDeque::from([(enemy.head, 0)]);
+        let mut space = 0;
+
+        while let Some((point, depth)) = queue.pop_front() {
+            for neighbor in self.grid.neighbors(&point) {
+                if visited.contains(&neighbor) || self.vacates_for(enemy, &neighbor) > depth + 1 {
+                    continue;
+                }
+
+                visited.insert(neighbor);
+                queue.push_back((neighbor, depth + 1));
+                space += 1;
+
+                // Once the enemy has plenty of room, it isn't trapped, and exactly how much room it has doesn't matter.
+                if space >= enemy.length + self.len() {
+                    return space;
+                }
+            }
+        }
+
+        space
+    }
+
+    /// Returns how many turns it will take for the segment at the given point to get out of `snake`'s way. See
+    /// `Grid::vacates_for`.
+    fn vacates_for(&self, snake: &Snake, point: &Point) -> usize {
+        match self.grid.slot(&snake.id) {
+            Some(slot) => self.grid.vacates_for(slot, point),
+            None => self.grid.vacates_in(point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{configuration::engine::EngineConfig, objects::testing::TestState};
+
+    /// Snake `b` can only get out of the top-left corner through (1, 6), right next to our head. Snake `c` is
+    /// already boxed into the bottom-right corner by our body.
+    fn hunting() -> TestState {
+        TestState::new(7, 7)
+            .snake(
+                'a',
+                90,
+                &[
+                    (2, 6),
+                    (3, 6),
+                    (4, 6),
+                    (5, 6),
+                    (5, 5),
+                    (5, 4),
+                    (5, 3),
+                    (5, 2),
+                    (5, 1),
+                    (5, 0),
+                    (4, 0),
+                ],
+            )
+            .snake('b', 90, &[(0, 6), (0, 5), (0, 4), (0, 3)])
+            .snake('c', 90, &[(6, 0), (6, 1), (6, 2)])
+    }
+
+    #[test]
+    fn only_enemies_we_trap_count_as_trapped() {
+        let engine = Engine::new(EngineConfig::default(), hunting().build());
+        let attack = engine.attack(&Point::new(1, 6), &engine.territory());
+
+        assert_eq!(attack.trapped, 1);
+        assert_eq!(engine.aggressive_move(), Some(Move::Left));
+    }
+
+    #[test]
+    fn the_length_advantage_comes_from_the_config() {
+        let mut config = EngineConfig::default();
+        assert!(Engine::new(config.clone(), hunting().build()).is_aggressive());
+
+        config.aggression.length_advantage = 8;
+        assert!(!Engine::new(config, hunting().build()).is_aggressive());
+    }
+}
@@ -0,0 +1,22 @@
+// Constrictor games have no food, and every snake grows every turn, so the board only ever fills up. The snake that
+// survives is the one that keeps the most space to itself, so that's all this mode cares about.
+
+use crate::{engine::Engine, objects::moves::Move};
+
+/// Engine API for constrictor moves.
+impl Engine {
+    /// Returns the move the snake should make in a constrictor game. This is the safe move that leaves the snake
+    /// with the most space it can reach, breaking ties by whether it leaves our allies a way out, and then by how
+    /// much of the board it controls.
+    pub fn constrictor_move(&self) -> Option<Move> {
+        let best = self.safe_neighbors(self.head()).max_by_key(|point| {
+            (
+                self.area_accessible(point).area,
+                !self.blocks_ally(point),
+                self.area_control(point),
+            )
+        })?;
+
+        self.move_between(self.head(), &best)
+    }
+}
@@ -1,30 +1,73 @@
 // Controlls when the snake decides that it's hungry, and *needs* to eat. Specifically, when the snake
-// is hungry, it will move towards the nearest food, even if it means moving into a wall.
+// is hungry, it will move towards the cheapest food it can reach alive, preferring food it gets to before anyone else.
 
-use crate::{engine::Engine, objects::moves::Move};
-use itertools::Itertools;
+use crate::{
+    engine::{utils::rcsp::FoodPath, Engine},
+    objects::{contest::Contest, moves::Move},
+};
 
 /// Engine API for hungry moves.
 impl Engine {
-    /// Returns the move the snake should make when it's hungry.
+    /// Returns the move the snake should make when it's hungry. This heads for the food we want (see
+    /// `food_target`), avoiding other snakes' heads on the way if we can.
     pub fn hungry_move(&self) -> Option<Move> {
-        // Get the nearest foods.
-        let nearest_foods = self
-            .food()
-            .copied()
-            .min_set_by_key(|f| f.distance(self.head()));
+        let food = self.food_target()?;
 
-        // Pathfind to the nearest food. If there is no path to the nearest food, return None.
-        let path = self.astar_find(self.head(), &nearest_foods)?;
+        // Pathfind to it, falling back to the path we know we survive if A* can't find one.
+        let path = self
+            .astar_find(self.head(), &[food.food])
+            .unwrap_or_else(|| food.path.clone());
 
         // Return the next move in the path. `path[0]` is the head of the snake, and `path[1]` is the
         // next move.
-        Some(Move::from_points(&path[0], &path[1]).expect("A* paths should generate valid moves."))
+        Some(
+            self.move_between(&path[0], &path[1])
+                .expect("Paths should generate valid moves."),
+        )
     }
 
-    /// Returns true if the snake is hungry. this is when the snake is below 50 health, or it's the
-    /// first 50 turns of the game.
+    /// Returns true if the snake is hungry, and there is food worth going for. The snake is hungry when it
+    /// needs food (see `needs_food`), or the food it would go for is far enough away (or behind enough hazards)
+    /// that it'd arrive with less than the configured hunger margin.
     pub fn is_hungry(&self) -> bool {
-        self.turn() <= self.config.hungry_moves || self.health() < 50
+        self.food_target()
+            .is_some_and(|food| self.needs_food() || food.health < self.config.hunger.margin)
+    }
+
+    /// Returns true if the snake needs food, whether or not there is any to get. This is when the snake is
+    /// below the configured hunger threshold, or it's one of the first `hungry_moves` turns of the game.
+    pub fn needs_food(&self) -> bool {
+        self.turn() <= self.config.hungry_moves || self.health() < self.config.hunger.threshold
+    }
+
+    /// Returns the food we want to go for: the cheapest food we can reach alive and get to before any enemy.
+    /// If there is none, and we are desperate (below the configured desperate health), we settle for food we
+    /// tie for, and then for any food at all. This is worked out once per turn, when the state is received.
+    pub fn food_target(&self) -> Option<&FoodPath> {
+        self.food.as_ref()
+    }
+}
+
+/// Private API for hungry moves.
+impl Engine {
+    /// Works out the food we want to go for this turn. See `food_target`.
+    pub(crate) fn find_food_target(&self) -> Option<FoodPath> {
+        let contests = self.food_contests();
+        let foods = |contest: Contest| {
+            contests
+                .iter()
+                .filter(|food| food.contest == contest)
+                .map(|food| food.food)
+                .collect::<Vec<_>>()
+        };
+
+        self.cheapest_food(&foods(Contest::Win)).or_else(|| {
+            if self.health() >= self.config.hunger.desperate {
+                return None;
+            }
+
+            self.cheapest_food(&foods(Contest::Tie))
+                .or_else(|| self.cheapest_food(&foods(Contest::Lose)))
+        })
     }
 }
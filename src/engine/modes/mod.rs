@@ -1,29 +1,21 @@
+pub mod aggressive;
+pub mod constrictor;
 pub mod hungry;
+pub mod searching;
+pub mod survival;
 
 use super::Engine;
 use log::debug;
 
-/// The different modes the snake can be in.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Mode {
-    /// The snake is hungry and wants to eat food.
-    Hungry,
-    // /// The snake is searching for a good move.
-    // Searching,
-}
-
 /// Engine API for modes.
 impl Engine {
-    /// Update the mode of the engine.
+    /// Update the mode of the engine, by picking the first strategy in the registry that applies to the
+    /// current state. If none do, the engine keeps the strategy it was using before.
     pub fn update_mode(&mut self) {
-        // self.mode = if self.is_hungry() {
-        //     Mode::Hungry
-        // } else {
-        //     Mode::Searching
-        // };
-
-        self.mode = Mode::Hungry;
+        if let Some(strategy) = self.strategies.select(self) {
+            self.strategy = strategy;
+        }
 
-        debug!("Engine mode: {:?}", self.mode);
+        debug!("Engine strategy: {}", self.strategy.name());
     }
 }
@@ -0,0 +1,71 @@
+// Controls what the snake does when it isn't hungry. Instead of following a fixed set of rules, the snake searches
+// through possible futures of the game, and picks the move that works out best. The algorithm it searches
+// with is picked in the engine configuration, and unfinished games are judged with the engine's evaluator.
+
+use crate::{
+    configuration::engine::SearchAlgorithm,
+    engine::{
+        search::{duct::Duct, mcts::Mcts, minimax::Minimax},
+        Engine,
+    },
+    objects::moves::Move,
+};
+
+/// Engine API for searching moves.
+impl Engine {
+    /// Returns the move the snake should make when it's searching for a good move.
+    pub fn searching_move(&self) -> Option<Move> {
+        let deadline = self.deadline();
+        let evaluator = self.evaluator.as_ref();
+
+        match self.config.search {
+            SearchAlgorithm::Mcts => {
+                Mcts::new(self.config.mcts, &self.state, deadline, evaluator).search()
+            }
+            SearchAlgorithm::Duct => {
+                Duct::new(self.config.mcts, &self.state, deadline, evaluator).search()
+            }
+            SearchAlgorithm::Minimax => {
+                Minimax::new(self.config.minimax, &self.state, deadline, evaluator).search()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::engine::EngineConfig,
+        objects::{grid::Grid, snake::SnakeID, testing::TestState, GameState},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn searches_use_the_engines_evaluator() {
+        let state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .snake('b', 90, &[(1, 1), (1, 2), (1, 3)])
+            .build();
+        let mut config = EngineConfig {
+            search: SearchAlgorithm::Minimax,
+            ..EngineConfig::default()
+        };
+        config.minimax.depth = 1;
+
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let counter = evaluations.clone();
+        let engine = Engine::new(config, state).with_evaluator(Arc::new(
+            move |_: &GameState, _: &Grid, _: &SnakeID| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                0.0
+            },
+        ));
+
+        assert!(engine.searching_move().is_some());
+        assert!(evaluations.load(Ordering::Relaxed) > 0);
+    }
+}
@@ -0,0 +1,102 @@
+// When there's no food we can get to, or no reason to go for any, the best thing to do is to stay alive. Following
+// our own tail is the safest way to do that: the cell our tail leaves behind is always free for us to move into, so
+// we can keep going around in a loop for as long as we need to.
+
+use crate::{engine::Engine, objects::moves::Move};
+
+/// Engine API for survival moves.
+impl Engine {
+    /// Returns the move the snake should make to survive. This follows our own tail if there's a path to it,
+    /// and otherwise moves into the region where we can still reach our tail, or failing that the largest one.
+    /// Moves that leave our allies a way out are preferred over ones that don't.
+    pub fn survival_move(&self) -> Option<Move> {
+        let tail = self.state.you.tail();
+
+        if let Some(path) = self.astar_find(self.head(), &[tail]) {
+            if path.len() > 1 {
+                return self.move_between(&path[0], &path[1]);
+            }
+        }
+
+        let best = self.safe_neighbors(self.head()).max_by_key(|point| {
+            let reach = self.area_accessible(point);
+            (reach.tail, !self.blocks_ally(point), reach.area)
+        })?;
+
+        self.move_between(self.head(), &best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::engine::EngineConfig,
+        objects::{point::Point, settings::GameType, testing::TestState},
+    };
+
+    /// Our body on an 8x2 board: the head is at (3, 0), and the body goes up and along the top row to the right,
+    /// splitting the board in two.
+    const BODY: [(i32, i32); 6] = [(3, 0), (3, 1), (4, 1), (5, 1), (6, 1), (7, 1)];
+
+    #[test]
+    fn we_follow_our_tail_when_there_is_a_path_to_it() {
+        let state = TestState::new(8, 2).snake('a', 90, &BODY).build();
+        let engine = Engine::new(EngineConfig::default(), state);
+
+        // The left has more room, but the path to our tail is on the right.
+        assert_eq!(engine.survival_move(), Some(Move::Right));
+    }
+
+    #[test]
+    fn without_a_path_to_our_tail_we_move_where_we_can_still_reach_it() {
+        // `b` is in the way of a path to our tail, but will have moved by the time we get there.
+        let state = TestState::new(8, 2)
+            .snake('a', 90, &BODY)
+            .snake('b', 90, &[(6, 0), (7, 0)])
+            .build();
+        let engine = Engine::new(EngineConfig::default(), state);
+
+        assert_eq!(
+            engine.astar_find(&Point::new(3, 0), &[Point::new(7, 1)]),
+            None
+        );
+        assert!(engine.area_accessible(&Point::new(4, 0)).tail);
+        assert_eq!(engine.survival_move(), Some(Move::Right));
+    }
+
+    #[test]
+    fn without_a_way_to_our_tail_we_move_into_the_largest_region() {
+        // Tails never move in constrictor games, so we can't follow ours, and the left has more room.
+        let state = TestState::new(8, 2)
+            .game_type(GameType::Constrictor)
+            .snake('a', 90, &BODY)
+            .build();
+        let engine = Engine::new(EngineConfig::default(), state);
+
+        assert_eq!(engine.survival_move(), Some(Move::Left));
+    }
+
+    #[test]
+    fn we_survive_when_we_need_food_but_there_is_none() {
+        let state = TestState::new(11, 11)
+            .snake('a', 30, &[(5, 5), (5, 4), (5, 3)])
+            .snake('b', 90, &[(1, 1), (1, 2), (1, 3)])
+            .build();
+        let mut engine = Engine::new(EngineConfig::default(), state.clone());
+        engine.update(state);
+
+        assert!(engine.needs_food());
+        assert_eq!(engine.strategy().name(), "survival");
+
+        // As soon as there is food to go for, we go for it instead.
+        let state = TestState::new(11, 11)
+            .snake('a', 30, &[(5, 5), (5, 4), (5, 3)])
+            .snake('b', 90, &[(1, 1), (1, 2), (1, 3)])
+            .food(&[(5, 7)])
+            .build();
+        engine.update(state);
+
+        assert_eq!(engine.strategy().name(), "hungry");
+    }
+}
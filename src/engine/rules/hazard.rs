@@ -2,27 +2,40 @@
 // are still dangerous to move into.
 
 use crate::{engine::Engine, objects::point::Point};
-use itertools::Itertools;
 
 /// Public Engine API for hazards.
 impl Engine {
     /// Returns true if the given point is a hazard, but does NOT consider if a point is safe to move to. This is
     /// because hazards are not always dangerous to move into, such as other snakes' moves.
     pub fn is_hazard(&self, point: &Point) -> bool {
-        self.hazards().contains(point) || self.is_snake_move(point)
+        self.grid.is_hazard(point) || self.is_snake_move(point)
     }
 
-    /// Returns true if the point is a possible move of any other snake than the Engine's, that is unsafe to move
+    /// Returns how much damage hazards would deal to us for moving into the given point `steps` moves from now.
+    /// Every hazard stacked on the point deals the ruleset's damage. Points that might have become hazards by
+    /// then (in royale games) count as one hazard, and eating food cancels the damage out entirely.
+    pub fn hazard_damage(&self, point: &Point, steps: u32) -> i32 {
+        if self.grid.is_food(point) {
+            return 0;
+        }
+
+        let mut stack = self.grid.hazards(point);
+
+        if stack == 0 && self.will_be_hazard(point, steps) {
+            stack = 1;
+        }
+
+        (stack * self.state.game.ruleset.settings.hazard_damage_per_turn) as i32
+    }
+
+    /// Returns true if the point is a possible move of any enemy snake (allies don't count), that is unsafe to move
     /// into IF the other snake moves into it. Otherwise, returns false.
     pub fn is_snake_move(&self, point: &Point) -> bool {
-        self.other_snakes()
+        self.enemy_snakes()
             .flat_map(|snake| {
-                snake
-                    .head
-                    .neighbors()
-                    .iter()
-                    .map(move |move_point| (*move_point, snake.length))
-                    .collect::<Vec<_>>()
+                self.neighbors(&snake.head)
+                    .into_iter()
+                    .map(move |move_point| (move_point, snake.length))
             })
             .any(|(move_point, length)| move_point == *point && self.len() <= length)
     }
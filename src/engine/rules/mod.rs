@@ -1,2 +1,5 @@
 pub mod hazard;
+pub mod royale;
 pub mod safety;
+pub mod squad;
+pub mod trap;
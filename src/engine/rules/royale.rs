@@ -0,0 +1,157 @@
+// In royale games, the hazards grow inwards from the edges of the board every few turns, one row or column at a time
+// from a random side. We can't know which side will shrink next, but we do know when it will happen, so we can tell
+// which cells *might* be hazards by the time we get to them, and avoid planning routes through them.
+
+use crate::{
+    engine::Engine,
+    objects::{grid::Grid, point::Point, settings::GameType, GameState},
+};
+
+/// A forecast of how the safe (non-hazard) area of a royale board will shrink over the next turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShrinkForecast {
+    /// The turn the forecast was made on.
+    turn: u32,
+
+    /// How many turns pass between every shrink.
+    every: u32,
+
+    /// The bottom-left and top-right corners of the safe area, or `None` if the whole board is already
+    /// covered in hazards.
+    safe: Option<(Point, Point)>,
+}
+
+impl ShrinkForecast {
+    /// Creates a forecast for the given state. Returns `None` if the game isn't a royale game, or the
+    /// board never shrinks.
+    pub fn new(state: &GameState, grid: &Grid) -> Option<Self> {
+        let every = state.game.ruleset.settings.royale.shrink_every_n_turns;
+
+        if state.game.ruleset.name != GameType::Royale || every == 0 {
+            return None;
+        }
+
+        let safe = (0..grid.width as i32)
+            .flat_map(|x| (0..grid.height as i32).map(move |y| Point::new(x, y)))
+            .filter(|point| !grid.is_hazard(point))
+            .fold(None, |bounds: Option<(Point, Point)>, point| {
+                let (min, max) = bounds.unwrap_or((point, point));
+                Some((
+                    Point::new(min.x.min(point.x), min.y.min(point.y)),
+                    Point::new(max.x.max(point.x), max.y.max(point.y)),
+                ))
+            });
+
+        Some(Self {
+            turn: state.turn,
+            every,
+            safe,
+        })
+    }
+
+    /// Returns how many times the board will have shrunk `turns` turns from now.
+    pub fn shrinks_in(&self, turns: u32) -> u32 {
+        (self.turn + turns) / self.every - self.turn / self.every
+    }
+
+    /// Returns true if the given point might be a hazard `turns` turns from now. This assumes the worst:
+    /// that every shrink until then happens on the side of the safe area closest to the point.
+    pub fn is_hazard_in(&self, point: &Point, turns: u32) -> bool {
+        let Some((min, max)) = self.safe else {
+            return true;
+        };
+
+        // How many shrinks it takes for the point to be covered. Points outside the safe area are already
+        // covered, so this is negative for them.
+        let margin = (point.x - min.x)
+            .min(max.x - point.x)
+            .min(point.y - min.y)
+            .min(max.y - point.y);
+
+        margin < self.shrinks_in(turns) as i32
+    }
+}
+
+/// Engine API for royale hazards.
+impl Engine {
+    /// Returns true if the given point might be a hazard `turns` turns from now, because the board will
+    /// have shrunk by then. Always returns false outside of royale games.
+    pub fn will_be_hazard(&self, point: &Point, turns: u32) -> bool {
+        self.forecast
+            .is_some_and(|forecast| forecast.is_hazard_in(point, turns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{configuration::engine::EngineConfig, objects::testing::TestState};
+
+    /// A royale game on turn 10 of an 11x11 board that shrinks every 25 turns, with the given hazards on it.
+    fn royale(hazards: &[(i32, i32)]) -> GameState {
+        TestState::new(11, 11)
+            .game_type(GameType::Royale)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .hazards(hazards)
+            .build()
+    }
+
+    fn forecast(state: &GameState) -> Option<ShrinkForecast> {
+        ShrinkForecast::new(state, &Grid::new(&state.board))
+    }
+
+    #[test]
+    fn shrinks_happen_every_n_turns() {
+        let forecast = forecast(&royale(&[])).expect("Royale games should have a forecast.");
+
+        // The board shrinks on turns 25, 50, 75 and so on.
+        assert_eq!(forecast.shrinks_in(0), 0);
+        assert_eq!(forecast.shrinks_in(14), 0);
+        assert_eq!(forecast.shrinks_in(15), 1);
+        assert_eq!(forecast.shrinks_in(39), 1);
+        assert_eq!(forecast.shrinks_in(40), 2);
+    }
+
+    #[test]
+    fn only_royale_games_shrink() {
+        let state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .build();
+
+        assert_eq!(forecast(&state), None);
+    }
+
+    #[test]
+    fn edges_are_covered_first() {
+        let forecast = forecast(&royale(&[])).expect("Royale games should have a forecast.");
+
+        // Every edge cell might be covered by the first shrink, and the cells next to them by the second.
+        for edge in [
+            Point::new(0, 5),
+            Point::new(10, 5),
+            Point::new(5, 0),
+            Point::new(5, 10),
+        ] {
+            assert!(!forecast.is_hazard_in(&edge, 14), "{edge:?}");
+            assert!(forecast.is_hazard_in(&edge, 15), "{edge:?}");
+        }
+
+        assert!(!forecast.is_hazard_in(&Point::new(1, 5), 15));
+        assert!(forecast.is_hazard_in(&Point::new(1, 5), 40));
+        assert!(!forecast.is_hazard_in(&Point::new(5, 5), 40));
+    }
+
+    #[test]
+    fn forecasts_start_from_the_hazards_already_on_the_board() {
+        // The left column and the bottom row are already covered.
+        let hazards = (0..11).flat_map(|i| [(0, i), (i, 0)]).collect::<Vec<_>>();
+        let engine = Engine::new(EngineConfig::default(), royale(&hazards));
+
+        assert!(engine.will_be_hazard(&Point::new(0, 5), 0));
+        assert!(!engine.will_be_hazard(&Point::new(1, 5), 14));
+        assert!(engine.will_be_hazard(&Point::new(1, 5), 15));
+        assert!(engine.will_be_hazard(&Point::new(5, 1), 15));
+        assert!(engine.will_be_hazard(&Point::new(10, 5), 15));
+        assert!(!engine.will_be_hazard(&Point::new(2, 5), 15));
+    }
+}
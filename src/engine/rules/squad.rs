@@ -0,0 +1,66 @@
+// Squad games are played in teams. Depending on the ruleset, squadmates can move through each other's bodies, and
+// share their health, length and fate. Either way, there's no point in treating allies like enemies: we don't need
+// to fear their heads, and we shouldn't trap them or steal the food they need.
+
+use crate::{
+    engine::Engine,
+    objects::{point::Point, settings::GameType, snake::Snake, snake::SnakeID},
+};
+
+/// Engine API for squads.
+impl Engine {
+    /// Returns true if the game is a squad game.
+    pub fn is_squad(&self) -> bool {
+        self.state.game.ruleset.name == GameType::Squad
+    }
+
+    /// Returns true if `snake` is on our squad. Outside of squad games, nobody is.
+    pub fn is_ally(&self, snake: &Snake) -> bool {
+        self.is_squad() && self.state.you.is_ally_of(snake)
+    }
+
+    /// Get all the other snakes that are on our squad.
+    pub fn allies(&self) -> impl Iterator<Item = &Snake> {
+        self.other_snakes().filter(move |snake| self.is_ally(snake))
+    }
+
+    /// Returns true if `mover` is allowed to move through the body of the snake with the given ID. This is
+    /// only the case in squad games that allow body collisions, and only for squadmates.
+    pub fn can_pass_through(&self, mover: &Snake, id: &SnakeID) -> bool {
+        self.is_squad()
+            && self.state.game.ruleset.settings.squad.allow_body_collisions
+            && self
+                .state
+                .board
+                .snakes
+                .get(id)
+                .is_some_and(|snake| mover.is_ally_of(snake))
+    }
+
+    /// Returns true if moving into the given point would take away the last way out an ally has left, which
+    /// would either trap it or make us collide head-to-head with it.
+    pub fn blocks_ally(&self, point: &Point) -> bool {
+        self.allies().any(|ally| {
+            let exits = self
+                .neighbors(&ally.head)
+                .into_iter()
+                .filter(|exit| !self.is_snake_for(exit, ally))
+                .collect::<Vec<_>>();
+
+            exits.len() == 1 && exits[0] == *point
+        })
+    }
+
+    /// Returns true if the given food is better left to an ally, because the ally is hungrier than us and
+    /// closer to it. If squads share health, one of us eating feeds all of us, so no food is ever left.
+    pub fn is_ally_food(&self, food: &Point) -> bool {
+        if self.state.game.ruleset.settings.squad.shared_health {
+            return false;
+        }
+
+        let distance = self.distance(self.head(), food);
+
+        self.allies()
+            .any(|ally| ally.health < self.health() && self.distance(&ally.head, food) < distance)
+    }
+}
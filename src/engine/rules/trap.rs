@@ -0,0 +1,169 @@
+// Keeps us out of pockets we can't fit in. Whatever move a mode comes up with, we check how big the region it leads
+// into is. If the region can't hold our body (and we can't follow our own tail out of it), we'd be trapping
+// ourselves, so we pick a move into a region we do fit in instead. If every region is too small, the largest one
+// buys us the most time. We'd also rather not trap an ally, so a move that takes an ally's last way out is swapped
+// for one that doesn't, as long as we still fit.
+
+use crate::{
+    engine::{utils::area::Reach, Engine},
+    objects::moves::Move,
+};
+use log::debug;
+
+/// Engine API for trap avoidance.
+impl Engine {
+    /// Checks the proposed move for traps, and returns the move we should make instead. The proposed move is
+    /// kept if the region it leads into can hold us and it doesn't take an ally's last way out, or if no other
+    /// safe move does better.
+    pub fn avoid_traps(&self, proposed: Move) -> Move {
+        if self.is_trap_free(proposed) {
+            return proposed;
+        }
+
+        let candidates = std::iter::once((proposed, self.reach(proposed)))
+            .chain(
+                self.safe_moves()
+                    .filter(|move_| *move_ != proposed)
+                    .map(|move_| (move_, self.reach(move_))),
+            )
+            .collect::<Vec<_>>();
+
+        // Prefer regions we fit in, then moves that leave our allies a way out, and then larger regions. The
+        // proposed move comes first, so it wins ties.
+        let (best, reach) = candidates
+            .iter()
+            .copied()
+            .rev()
+            .max_by_key(|(move_, reach)| {
+                (self.fits(reach), !self.blocks_ally_with(*move_), reach.area)
+            })
+            .expect("There should always be at least the proposed move.");
+
+        if best != proposed {
+            debug!(
+                "Avoiding a trap: moving {best} into {} cells instead of {proposed}",
+                reach.area
+            );
+        }
+
+        best
+    }
+
+    /// Returns true if the given move leads into a region we fit in, without taking an ally's last way out.
+    /// Unsafe moves never are.
+    pub fn is_trap_free(&self, move_: Move) -> bool {
+        self.fits(&self.reach(move_)) && !self.blocks_ally_with(move_)
+    }
+}
+
+/// Private API for trap avoidance.
+impl Engine {
+    /// Returns true if we can fit in a region, either because it's at least as big as we are long, or because
+    /// we can get to our own tail in it.
+    fn fits(&self, reach: &Reach) -> bool {
+        reach.tail || reach.area >= self.len()
+    }
+
+    /// Returns true if making `move_` would take an ally's last way out.
+    fn blocks_ally_with(&self, move_: Move) -> bool {
+        self.blocks_ally(&self.state.board.move_point(self.head(), move_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::engine::EngineConfig,
+        objects::{point::Point, settings::GameType, testing::TestState},
+    };
+
+    #[test]
+    fn taking_an_allys_last_way_out_is_avoided_but_not_unsafe() {
+        // Our ally's head is at (6, 6), and the only way out it has left is (5, 6), right above our head.
+        let state = TestState::new(11, 11)
+            .game_type(GameType::Squad)
+            .squad_snake('a', "red", 90, &[(5, 5), (5, 4), (5, 3)])
+            .squad_snake(
+                'b',
+                "red",
+                90,
+                &[
+                    (6, 6),
+                    (7, 6),
+                    (7, 5),
+                    (6, 5),
+                    (6, 4),
+                    (7, 4),
+                    (8, 4),
+                    (8, 5),
+                    (8, 6),
+                    (8, 7),
+                    (7, 7),
+                    (6, 7),
+                    (6, 8),
+                    (6, 9),
+                ],
+            )
+            .build();
+        let engine = Engine::new(EngineConfig::default(), state);
+
+        assert!(engine.blocks_ally(&Point::new(5, 6)));
+        assert!(!engine.is_unsafe(&Point::new(5, 6)));
+        assert_eq!(engine.avoid_traps(Move::Up), Move::Left);
+        assert_eq!(engine.avoid_traps(Move::Left), Move::Left);
+    }
+
+    /// An 8x2 constrictor board (where nothing ever moves out of the way), with our head at (3, 0) and our body
+    /// going up and along the top row, so that moving left leads into 6 cells and moving right into fewer.
+    fn corridor(length: usize) -> Engine {
+        let body = [(3, 0), (3, 1), (4, 1), (5, 1), (6, 1), (7, 1), (7, 0)];
+        let state = TestState::new(8, 2)
+            .game_type(GameType::Constrictor)
+            .snake('a', 90, &body[..length])
+            .build();
+
+        Engine::new(EngineConfig::default(), state)
+    }
+
+    #[test]
+    fn regions_we_dont_fit_in_are_avoided() {
+        // Moving right leads into 4 cells, which can't hold all 6 of us.
+        let engine = corridor(6);
+
+        assert_eq!(engine.reach(Move::Left).area, 6);
+        assert_eq!(engine.reach(Move::Right).area, 4);
+        assert_eq!(engine.avoid_traps(Move::Right), Move::Left);
+        assert_eq!(engine.avoid_traps(Move::Left), Move::Left);
+    }
+
+    #[test]
+    fn unsafe_moves_are_avoided() {
+        // Moving up runs into our own body, which doesn't lead anywhere.
+        let engine = corridor(6);
+
+        assert_eq!(engine.reach(Move::Up), Reach::default());
+        assert_eq!(engine.avoid_traps(Move::Up), Move::Left);
+    }
+
+    #[test]
+    fn regions_we_can_follow_our_tail_out_of_are_fine() {
+        let mut engine = corridor(6);
+        engine.reaches[Move::Right.index()] = Reach {
+            area: 4,
+            tail: true,
+        };
+
+        assert_eq!(engine.avoid_traps(Move::Right), Move::Right);
+    }
+
+    #[test]
+    fn the_largest_region_is_picked_when_we_fit_in_none() {
+        // Neither side can hold all 7 of us, but there is more room to the left.
+        let engine = corridor(7);
+
+        assert_eq!(engine.reach(Move::Right).area, 3);
+        assert_eq!(engine.avoid_traps(Move::Right), Move::Left);
+        assert_eq!(engine.avoid_traps(Move::Left), Move::Left);
+    }
+}
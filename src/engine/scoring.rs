@@ -0,0 +1,271 @@
+// Explains the engine's options. Every turn there are only four moves to pick from, and when something goes wrong
+// it's useful to know what the engine thought of each of them. This scores every move on how safe it is and how
+// much room it leaves us, and writes down why, so the scores can be logged and checked. Once the engine has picked
+// a move, the scores also say what the strategy thought of each move, and which one was made.
+
+use super::Engine;
+use crate::objects::{moves::Move, point::Point};
+use std::fmt::{self, Display, Formatter};
+
+/// How safe a move is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Safety {
+    /// The move kills us.
+    Deadly,
+
+    /// The move might kill us, or get us into trouble.
+    Risky,
+
+    /// Nothing bad happens right away.
+    Safe,
+}
+
+/// What the engine thinks of a single move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveScore {
+    /// The move.
+    pub move_: Move,
+
+    /// How good the move is. Higher is better. Deadly moves always score lower than risky moves, which always
+    /// score lower than safe moves.
+    pub score: f64,
+
+    /// How safe the move is.
+    pub safety: Safety,
+
+    /// Why the move scored the way it did.
+    pub reasons: Vec<String>,
+
+    /// The score the strategy gave the move on the last turn, if it considered making it at all.
+    pub proposal: Option<f64>,
+
+    /// Whether the engine made this move on the last turn.
+    pub chosen: bool,
+}
+
+impl Display for MoveScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({:?}, {:.1}): {}",
+            self.move_,
+            self.safety,
+            self.score,
+            self.reasons.join(", ")
+        )
+    }
+}
+
+/// Engine API for scoring moves.
+impl Engine {
+    /// Scores all four moves, and returns them from best to worst. This only looks at the rules and the shape
+    /// of the board, and doesn't ask the strategy (or run a search), so it's cheap enough to call every turn.
+    /// What the strategy thought of every move, and the move that was made, come from `get_move` (if it has
+    /// been called since the last `update`).
+    pub fn score_moves(&self) -> Vec<MoveScore> {
+        let mut scores = Move::all()
+            .into_iter()
+            .map(|move_| self.score_move(move_))
+            .collect::<Vec<_>>();
+
+        scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scores
+    }
+}
+
+/// Private API for scoring moves.
+impl Engine {
+    /// Scores a single move.
+    fn score_move(&self, move_: Move) -> MoveScore {
+        let point = self.state.board.move_point(self.head(), move_);
+        let mut score = MoveScore {
+            move_,
+            score: 0.0,
+            safety: Safety::Safe,
+            reasons: Vec::new(),
+            proposal: None,
+            chosen: self.chosen == Some(move_),
+        };
+
+        if !self.is_on_board(&point) {
+            score.deadly("runs into a wall");
+        } else if self.is_snake(&point) {
+            score.deadly("runs into a snake's body");
+        } else {
+            self.score_point(move_, &point, &mut score);
+        }
+
+        score.score += match score.safety {
+            Safety::Deadly => -1_000_000.0,
+            Safety::Risky => -1_000.0,
+            Safety::Safe => 0.0,
+        };
+
+        if let Some((_, proposal)) = self.proposals.iter().find(|(other, _)| *other == move_) {
+            score.proposal = Some(*proposal);
+            score.reasons.push(format!(
+                "{} strategy scored it {proposal:.2}",
+                self.strategy.name()
+            ));
+        }
+
+        if score.chosen {
+            score.reasons.push("chosen".into());
+        }
+
+        score
+    }
+
+    /// Scores making a move into a point that's on the board and not in a body.
+    fn score_point(&self, move_: Move, point: &Point, score: &mut MoveScore) {
+        for enemy in self.enemy_snakes() {
+            if !self.neighbors(&enemy.head).contains(point) {
+                continue;
+            }
+
+            if enemy.length >= self.len() {
+                score.risky("possible head-to-head with equal or longer snake");
+            } else {
+                score
+                    .reasons
+                    .push("possible head-to-head with shorter snake".into());
+                score.score += 10.0;
+            }
+        }
+
+        if self.blocks_ally(point) {
+            score.risky("takes an ally's last way out");
+        }
+
+        if self.grid.is_food(point) {
+            score.reasons.push("eats food".into());
+            score.score += 10.0;
+        }
+
+        let damage = self.hazard_damage(point, 1);
+
+        if damage > 0 {
+            score.reasons.push(format!("takes {damage} hazard damage"));
+            score.score -= damage as f64;
+
+            if self.health() - 1 - damage <= 0 {
+                score.deadly("runs out of health in a hazard");
+            }
+        }
+
+        let reach = self.reach(move_);
+        score
+            .reasons
+            .push(format!("leads into {}-cell region", reach.area));
+        score.score += reach.area as f64;
+
+        if reach.tail {
+            score.reasons.push("can reach our own tail".into());
+        } else if reach.area < self.len() {
+            score.risky("region is too small to hold us");
+        }
+    }
+}
+
+impl MoveScore {
+    /// Marks the move as deadly, because of `reason`.
+    fn deadly(&mut self, reason: &str) {
+        self.safety = Safety::Deadly;
+        self.reasons.push(reason.into());
+    }
+
+    /// Marks the move as risky (unless it's already deadly), because of `reason`.
+    fn risky(&mut self, reason: &str) {
+        self.safety = self.safety.min(Safety::Risky);
+        self.reasons.push(reason.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::engine::EngineConfig,
+        engine::strategy::{StrategyRegistry, Survival},
+        objects::testing::TestState,
+    };
+
+    /// Our head is against the left wall, with our body below it, an enemy as long as us above it, and food to
+    /// the right.
+    fn cornered() -> Engine {
+        let state = TestState::new(11, 11)
+            .snake('a', 90, &[(0, 5), (0, 4), (0, 3)])
+            .snake('b', 90, &[(0, 7), (0, 8), (0, 9)])
+            .food(&[(1, 5)])
+            .build();
+        let mut engine = Engine::with_strategies(
+            EngineConfig::default(),
+            state.clone(),
+            StrategyRegistry::new().register(Survival),
+        );
+        engine.update(state);
+        engine
+    }
+
+    /// Finds the score for the given move.
+    fn score_for(scores: &[MoveScore], move_: Move) -> &MoveScore {
+        scores
+            .iter()
+            .find(|score| score.move_ == move_)
+            .expect("Every move should be scored.")
+    }
+
+    #[test]
+    fn moves_are_scored_with_their_reasons() {
+        let scores = cornered().score_moves();
+
+        let left = score_for(&scores, Move::Left);
+        assert_eq!(left.safety, Safety::Deadly);
+        assert_eq!(left.reasons, vec!["runs into a wall"]);
+
+        let down = score_for(&scores, Move::Down);
+        assert_eq!(down.safety, Safety::Deadly);
+        assert_eq!(down.reasons, vec!["runs into a snake's body"]);
+
+        let up = score_for(&scores, Move::Up);
+        assert_eq!(up.safety, Safety::Risky);
+        assert_eq!(
+            up.reasons[0],
+            "possible head-to-head with equal or longer snake"
+        );
+
+        let right = score_for(&scores, Move::Right);
+        assert_eq!(right.safety, Safety::Safe);
+        assert_eq!(right.reasons[0], "eats food");
+        assert!(right.reasons[1].starts_with("leads into"));
+
+        // Safe moves come before risky ones, which come before deadly ones.
+        assert_eq!(
+            scores
+                .iter()
+                .map(|score| score.move_)
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![Move::Right, Move::Up]
+        );
+        assert!(scores
+            .iter()
+            .all(|score| score.proposal.is_none() && !score.chosen));
+    }
+
+    #[test]
+    fn scores_include_the_strategys_proposal_once_a_move_is_made() {
+        let mut engine = cornered();
+        let chosen = engine.get_move();
+        let scores = engine.score_moves();
+        let score = score_for(&scores, chosen);
+
+        assert_eq!(engine.strategy().name(), "survival");
+        assert!(score.chosen);
+        assert_eq!(score.proposal, Some(1.0));
+        assert!(score
+            .reasons
+            .ends_with(&["survival strategy scored it 1.00".into(), "chosen".into()]));
+        assert_eq!(scores.iter().filter(|score| score.chosen).count(), 1);
+    }
+}
@@ -0,0 +1,313 @@
+// This is a Decoupled UCT (DUCT) search. Battlesnake is a simultaneous-move game, so instead of pretending the snakes
+// take turns, every node keeps separate statistics for every snake's moves. Each snake picks its own move with UCT
+// based only on its own statistics, and the combination of all of their moves (the joint move) decides which child
+// we walk down to. This way, every snake is assumed to play well, but none of them gets to see the others' moves
+// before it makes its own.
+
+use super::{is_running, play_out, squash, Stats};
+use crate::{
+    configuration::mcts::MCTSConfig,
+    engine::evaluation::Evaluator,
+    objects::{grid::Grid, moves::Move, snake::SnakeID, GameState},
+};
+use log::debug;
+use std::{collections::HashMap, time::Instant};
+
+/// The Decoupled UCT search.
+pub struct Duct<'a, E: ?Sized> {
+    /// The configuration for the search. DUCT is a variant of MCTS, so it shares its configuration.
+    config: MCTSConfig,
+
+    /// The state the search starts from.
+    root: &'a GameState,
+
+    /// When the search has to stop by.
+    deadline: Instant,
+
+    /// The ID of the snake we are searching for.
+    you: SnakeID,
+
+    /// The evaluator for games that haven't been decided by the time a play out stops.
+    evaluator: &'a E,
+
+    /// All the nodes in the tree. The root is always the first node.
+    nodes: Vec<Node>,
+}
+
+/// Public API for DUCT.
+impl<'a, E: Evaluator + ?Sized> Duct<'a, E> {
+    /// Creates a new search, starting from the given state, that has to stop by `deadline`.
+    pub fn new(
+        config: MCTSConfig,
+        root: &'a GameState,
+        deadline: Instant,
+        evaluator: &'a E,
+    ) -> Self {
+        Self {
+            config,
+            root,
+            deadline,
+            you: root.you.id,
+            evaluator,
+            nodes: vec![Node::new(root)],
+        }
+    }
+
+    /// Runs the search until it runs out of time or iterations, and returns the move we explored the
+    /// most from the root. Returns `None` if we are not on the board anymore.
+    pub fn search(&mut self) -> Option<Move> {
+        let mut iterations = 0;
+
+        while iterations < self.config.iterations && Instant::now() < self.deadline {
+            self.iterate();
+            iterations += 1;
+        }
+
+        debug!("DUCT: ran {iterations} iterations");
+
+        let root = &self.nodes[0];
+        let snake = root.snakes.iter().position(|id| *id == self.you)?;
+
+        for (move_, stats) in root.moves[snake].iter().zip(&root.stats[snake]) {
+            debug!(
+                "DUCT: {move_} was visited {} times with an average value of {:.3}",
+                stats.visits,
+                stats.mean()
+            );
+        }
+
+        root.moves[snake]
+            .iter()
+            .zip(&root.stats[snake])
+            .max_by_key(|(_, stats)| stats.visits)
+            .map(|(move_, _)| *move_)
+    }
+}
+
+/// Private API for DUCT.
+impl<'a, E: Evaluator + ?Sized> Duct<'a, E> {
+    /// Runs a single iteration of the search: walk down the tree with every snake picking its own move,
+    /// add a new node to it, play out some games from there, and then update every snake's statistics
+    /// for the moves it made on the way down.
+    fn iterate(&mut self) {
+        let mut state = self.root.clone();
+        let mut node = 0;
+        // The nodes we walked through, and the index of the move each snake made from them.
+        let mut path: Vec<(usize, Vec<usize>)> = Vec::new();
+        let mut depth = 0;
+
+        while is_running(&state, &self.you, depth, self.config.max_depth) {
+            let joint = self.select(node);
+            let moves = self.nodes[node].joint_moves(&joint);
+
+            state.advance(&moves);
+            depth += 1;
+            path.push((node, joint.clone()));
+
+            match self.nodes[node].children.get(&joint) {
+                Some(&child) => node = child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::new(&state));
+                    self.nodes[node].children.insert(joint, child);
+                    break;
+                }
+            }
+        }
+
+        let rewards = self.play_out(&state, depth);
+
+        for (node, joint) in path {
+            let node = &mut self.nodes[node];
+            node.visits += 1;
+
+            for (snake, &move_) in joint.iter().enumerate() {
+                let reward = rewards.get(&node.snakes[snake]).copied().unwrap_or(0.0);
+                node.stats[snake][move_].update(reward);
+            }
+        }
+    }
+
+    /// Has every snake at the given node pick the index of its move with UCT, using only its own
+    /// statistics. Moves that have never been tried are always picked first.
+    fn select(&self, node: usize) -> Vec<usize> {
+        let node = &self.nodes[node];
+        let parent_visits = node.visits.max(1) as f64;
+
+        node.stats
+            .iter()
+            .map(|stats| {
+                (0..stats.len())
+                    .max_by(|&a, &b| {
+                        let a = stats[a].uct(parent_visits, self.config.exploration);
+                        let b = stats[b].uct(parent_visits, self.config.exploration);
+                        a.total_cmp(&b)
+                    })
+                    .expect("Every snake should have at least one move.")
+            })
+            .collect()
+    }
+
+    /// Plays out `games_per_search` random games in parallel from the given state, and returns the
+    /// average reward of every snake over the games.
+    fn play_out(&self, state: &GameState, depth: u32) -> HashMap<SnakeID, f64> {
+        let games = play_out(&self.config, state, &self.you, depth, |state, grid| {
+            self.rewards(state, grid)
+        });
+        let mut total = HashMap::new();
+
+        for rewards in &games {
+            for (id, reward) in rewards {
+                *total.entry(*id).or_insert(0.0) += reward;
+            }
+        }
+
+        for reward in total.values_mut() {
+            *reward /= games.len() as f64;
+        }
+
+        total
+    }
+
+    /// The reward of every snake that started the search for a finished (or cut-off) game (whose grid is `grid`):
+    /// 1 if it won, 0 if it died, and what the evaluator thinks of the game for it (squashed to between 0 and 1)
+    /// if the game hasn't been decided for it yet.
+    fn rewards(&self, state: &GameState, grid: &Grid) -> HashMap<SnakeID, f64> {
+        self.root
+            .board
+            .snakes
+            .keys()
+            .map(|id| {
+                let reward = if !state.is_alive(id) {
+                    0.0
+                } else if state.is_over() {
+                    1.0
+                } else {
+                    squash(self.evaluator.evaluate(state, grid, id))
+                };
+
+                (*id, reward)
+            })
+            .collect()
+    }
+}
+
+/// A node in the search tree, reached by making a specific sequence of joint moves from the root. The
+/// game is deterministic (we don't simulate food spawning), so every node always has the same state.
+#[derive(Debug, Clone)]
+struct Node {
+    /// The snakes that are alive at this node.
+    snakes: Vec<SnakeID>,
+
+    /// The moves every snake can pick from at this node, in the same order as `snakes`.
+    moves: Vec<Vec<Move>>,
+
+    /// The statistics of every snake's moves, in the same order as `moves`.
+    stats: Vec<Vec<Stats>>,
+
+    /// How many times the node has been visited.
+    visits: u32,
+
+    /// The children of this node, indexed by the index of the move every snake made.
+    children: HashMap<Vec<usize>, usize>,
+}
+
+impl Node {
+    /// Creates a new node for the given state. Every snake can pick from its safe moves, or from any
+    /// move if it has none.
+    fn new(state: &GameState) -> Self {
+        let grid = Grid::new(&state.board);
+        let snakes = grid
+            .snakes()
+            .iter()
+            .map(|snake| snake.id)
+            .collect::<Vec<_>>();
+
+        let moves = (0..snakes.len())
+            .map(|slot| {
+                let moves = grid.safe_moves(slot);
+                if moves.is_empty() {
+                    Move::all().to_vec()
+                } else {
+                    moves
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let stats = moves
+            .iter()
+            .map(|moves| vec![Stats::default(); moves.len()])
+            .collect();
+
+        Self {
+            snakes,
+            moves,
+            stats,
+            visits: 0,
+            children: HashMap::new(),
+        }
+    }
+
+    /// Turns the indices of the moves every snake made into the moves themselves.
+    fn joint_moves(&self, joint: &[usize]) -> HashMap<SnakeID, Move> {
+        self.snakes
+            .iter()
+            .zip(joint)
+            .enumerate()
+            .map(|(snake, (id, &move_))| (*id, self.moves[snake][move_]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::evaluation::WeightedEvaluator, objects::testing::TestState};
+    use std::time::Duration;
+
+    /// We are on the left edge of the board with our body below us, so moving left or down kills us.
+    fn cornered() -> GameState {
+        TestState::new(11, 11)
+            .snake('a', 90, &[(0, 5), (0, 4), (0, 3)])
+            .snake('b', 90, &[(8, 5), (8, 4), (8, 3)])
+            .build()
+    }
+
+    #[test]
+    fn lethal_moves_are_avoided() {
+        let state = cornered();
+        let config = MCTSConfig {
+            iterations: 50,
+            max_depth: 10,
+            ..MCTSConfig::default()
+        };
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        let move_ = Duct::new(config, &state, deadline, &WeightedEvaluator::default()).search();
+
+        assert!(matches!(move_, Some(Move::Up | Move::Right)), "{move_:?}");
+    }
+
+    #[test]
+    fn the_search_stops_at_the_deadline() {
+        let state = cornered();
+        let start = Instant::now();
+        let deadline = start + Duration::from_millis(50);
+
+        let move_ = Duct::new(
+            MCTSConfig::default(),
+            &state,
+            deadline,
+            &WeightedEvaluator::default(),
+        )
+        .search();
+
+        // The search can only stop between steps, so it may run a little over.
+        assert!(move_.is_some());
+        assert!(
+            start.elapsed() < Duration::from_millis(250),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+}
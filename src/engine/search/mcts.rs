@@ -0,0 +1,254 @@
+// This is an open-loop Monte Carlo Tree Search over our own moves. The tree only stores our moves, and the other
+// snakes' moves are sampled at random every time we walk down the tree. This way, the tree doesn't have to store any
+// game states, and it doesn't get fooled by a single lucky (or unlucky) sample of the other snakes' moves.
+
+use super::{is_running, play_out, random_moves, squash, Stats};
+use crate::{
+    configuration::mcts::MCTSConfig,
+    engine::evaluation::Evaluator,
+    objects::{grid::Grid, moves::Move, snake::SnakeID, GameState},
+};
+use log::debug;
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+use std::time::Instant;
+
+/// The Monte Carlo Tree Search.
+pub struct Mcts<'a, E: ?Sized> {
+    /// The configuration for the search.
+    config: MCTSConfig,
+
+    /// The state the search starts from.
+    root: &'a GameState,
+
+    /// When the search has to stop by.
+    deadline: Instant,
+
+    /// The ID of the snake we are searching for.
+    you: SnakeID,
+
+    /// The evaluator for games that haven't been decided by the time a play out stops.
+    evaluator: &'a E,
+
+    /// All the nodes in the tree. The root is always the first node.
+    nodes: Vec<Node>,
+
+    /// The random number generator for the search.
+    rng: SmallRng,
+}
+
+/// Public API for the MCTS.
+impl<'a, E: Evaluator + ?Sized> Mcts<'a, E> {
+    /// Creates a new search, starting from the given state, that has to stop by `deadline`.
+    pub fn new(
+        config: MCTSConfig,
+        root: &'a GameState,
+        deadline: Instant,
+        evaluator: &'a E,
+    ) -> Self {
+        Self {
+            config,
+            root,
+            deadline,
+            you: root.you.id,
+            evaluator,
+            nodes: vec![Node::default()],
+            rng: SmallRng::from_entropy(),
+        }
+    }
+
+    /// Runs the search until it runs out of time or iterations, and returns the move that was explored
+    /// the most. Returns `None` if the search never got to try any moves (i.e. the game is already over).
+    pub fn search(&mut self) -> Option<Move> {
+        let mut iterations = 0;
+
+        while iterations < self.config.iterations && Instant::now() < self.deadline {
+            self.iterate();
+            iterations += 1;
+        }
+
+        debug!("MCTS: ran {iterations} iterations");
+
+        for (move_, child) in self.children(0) {
+            let node = &self.nodes[child];
+            debug!(
+                "MCTS: {move_} was visited {} times with an average value of {:.3}",
+                node.stats.visits,
+                node.stats.mean()
+            );
+        }
+
+        self.children(0)
+            .max_by_key(|(_, child)| self.nodes[*child].stats.visits)
+            .map(|(move_, _)| move_)
+    }
+}
+
+/// Private API for the MCTS.
+impl<'a, E: Evaluator + ?Sized> Mcts<'a, E> {
+    /// Runs a single iteration of the search: walk down the tree, add a new node to it, play out some games
+    /// from there, and then update every node we walked through with the results.
+    fn iterate(&mut self) {
+        let mut state = self.root.clone();
+        let mut node = 0;
+        let mut path = vec![0];
+        let mut depth = 0;
+
+        while is_running(&state, &self.you, depth, self.config.max_depth) {
+            let grid = Grid::new(&state.board);
+            let mut moves = grid
+                .slot(&self.you)
+                .map(|slot| grid.safe_moves(slot))
+                .unwrap_or_default();
+
+            // If we have no safe moves, we are going to die anyways, so just pick any move.
+            if moves.is_empty() {
+                moves = Move::all().to_vec();
+            }
+
+            let untried = moves
+                .iter()
+                .copied()
+                .filter(|move_| self.nodes[node].children[move_.index()].is_none())
+                .collect::<Vec<_>>();
+
+            // If there are moves we haven't tried from here yet, expand the tree with one of them, and stop
+            // walking down the tree. Otherwise, follow the move with the best UCT score.
+            let (move_, expanded) = match untried.choose(&mut self.rng) {
+                Some(&move_) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children[move_.index()] = Some(child);
+                    (move_, true)
+                }
+                None => (self.select(node, &moves), false),
+            };
+
+            self.advance(&mut state, &grid, move_);
+            node = self.nodes[node].children[move_.index()].expect("The child should exist.");
+            path.push(node);
+            depth += 1;
+
+            if expanded {
+                break;
+            }
+        }
+
+        let rewards = play_out(&self.config, &state, &self.you, depth, |state, grid| {
+            self.reward(state, grid)
+        });
+        let reward = rewards.iter().sum::<f64>() / rewards.len() as f64;
+
+        for node in path {
+            self.nodes[node].stats.update(reward);
+        }
+    }
+
+    /// Selects the move out of `moves` with the best UCT score from the given node. All the moves must
+    /// already have children.
+    fn select(&self, node: usize, moves: &[Move]) -> Move {
+        let parent_visits = self.nodes[node].stats.visits as f64;
+        let uct = |move_: &Move| {
+            let child = self.nodes[node].children[move_.index()].expect("The child should exist.");
+            self.nodes[child]
+                .stats
+                .uct(parent_visits, self.config.exploration)
+        };
+
+        moves
+            .iter()
+            .copied()
+            .max_by(|a, b| uct(a).total_cmp(&uct(b)))
+            .expect("There should always be at least one move to select from.")
+    }
+
+    /// Advances the state (whose grid is `grid`) by one turn, where we make the given move, and every other
+    /// snake makes a random move.
+    fn advance(&mut self, state: &mut GameState, grid: &Grid, move_: Move) {
+        let mut moves = random_moves(grid, &mut self.rng);
+        moves.insert(self.you, move_);
+        state.advance(&moves);
+    }
+
+    /// The reward for a finished (or cut-off) game (whose grid is `grid`): 1 if we won, 0 if we died, and what
+    /// the evaluator thinks of the game (squashed to between 0 and 1) if it hasn't been decided yet.
+    fn reward(&self, state: &GameState, grid: &Grid) -> f64 {
+        if !state.is_alive(&self.you) {
+            0.0
+        } else if state.is_over() {
+            1.0
+        } else {
+            squash(self.evaluator.evaluate(state, grid, &self.you))
+        }
+    }
+
+    /// Returns an iterator over all the moves that have been tried from the given node, and the child
+    /// they lead to.
+    fn children(&self, node: usize) -> impl Iterator<Item = (Move, usize)> + '_ {
+        Move::all()
+            .into_iter()
+            .filter_map(move |move_| Some((move_, self.nodes[node].children[move_.index()]?)))
+    }
+}
+
+/// A node in the search tree, reached by making a specific sequence of moves from the root.
+#[derive(Debug, Default, Clone)]
+struct Node {
+    /// The statistics of every game played through this node.
+    stats: Stats,
+
+    /// The children of this node, indexed by the move that leads to them.
+    children: [Option<usize>; 4],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::evaluation::WeightedEvaluator, objects::testing::TestState};
+    use std::time::Duration;
+
+    /// We are on the left edge of the board with our body below us, so moving left or down kills us.
+    fn cornered() -> GameState {
+        TestState::new(11, 11)
+            .snake('a', 90, &[(0, 5), (0, 4), (0, 3)])
+            .snake('b', 90, &[(8, 5), (8, 4), (8, 3)])
+            .build()
+    }
+
+    #[test]
+    fn lethal_moves_are_avoided() {
+        let state = cornered();
+        let config = MCTSConfig {
+            iterations: 50,
+            max_depth: 10,
+            ..MCTSConfig::default()
+        };
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        let move_ = Mcts::new(config, &state, deadline, &WeightedEvaluator::default()).search();
+
+        assert!(matches!(move_, Some(Move::Up | Move::Right)), "{move_:?}");
+    }
+
+    #[test]
+    fn the_search_stops_at_the_deadline() {
+        let state = cornered();
+        let start = Instant::now();
+        let deadline = start + Duration::from_millis(50);
+
+        let move_ = Mcts::new(
+            MCTSConfig::default(),
+            &state,
+            deadline,
+            &WeightedEvaluator::default(),
+        )
+        .search();
+
+        // The search can only stop between steps, so it may run a little over.
+        assert!(move_.is_some());
+        assert!(
+            start.elapsed() < Duration::from_millis(250),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+}
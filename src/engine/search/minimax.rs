@@ -0,0 +1,421 @@
+// This is a depth-limited minimax search with alpha-beta pruning. Every turn is split in two: first we pick our move,
+// then all the other snakes pick their moves together, knowing what we did. This is the "paranoid" assumption: in a
+// free-for-all, we act as if every other snake is working together to kill us. In a duel, there is only one other
+// snake, so this is just plain alpha-beta. The search deepens one turn at a time until it runs out of time, so it
+// always has the best move from the deepest search it finished. Unlike MCTS, the search is deterministic, so given
+// enough time, the same state always leads to the same move. States we have already searched are remembered in a
+// transposition table, so that reaching them again through different moves doesn't cost anything. The whole search
+// runs on a single copy of the state, applying and undoing turns as it walks up and down the tree.
+
+use super::{
+    table::{Bound, Entry, TranspositionTable},
+    zobrist::Zobrist,
+};
+use crate::{
+    configuration::minimax::MinimaxConfig,
+    engine::evaluation::Evaluator,
+    objects::{grid::Grid, moves::Move, simulator::TurnDelta, snake::SnakeID, GameState},
+};
+use itertools::Itertools;
+use log::debug;
+use std::{collections::HashMap, time::Instant};
+
+/// The value of a state where we have won the game.
+const WIN: f64 = 1_000.0;
+
+/// The value of a state where we have lost the game.
+const LOSS: f64 = -WIN;
+
+/// The minimax search. `E` is the evaluator used for states that aren't finished yet.
+pub struct Minimax<'a, E: ?Sized> {
+    /// The configuration for the search.
+    config: MinimaxConfig,
+
+    /// The state the search starts from.
+    root: &'a GameState,
+
+    /// When the search has to stop by.
+    deadline: Instant,
+
+    /// Whether the search has run out of time.
+    timed_out: bool,
+
+    /// The ID of the snake we are searching for.
+    you: SnakeID,
+
+    /// The evaluator for states that aren't finished yet.
+    evaluator: &'a E,
+
+    /// The keys for hashing states.
+    zobrist: Zobrist,
+
+    /// The hash of the state the search is currently at. This is kept up to date as turns are applied and
+    /// undone, instead of hashing every state from scratch.
+    hash: u64,
+
+    /// The results of states that have already been searched.
+    table: TranspositionTable,
+
+    /// The number of states that have been searched so far.
+    nodes: u64,
+}
+
+/// Public API for minimax.
+impl<'a, E: Evaluator + ?Sized> Minimax<'a, E> {
+    /// Creates a new search, starting from the given state, that has to stop by `deadline`.
+    pub fn new(
+        config: MinimaxConfig,
+        root: &'a GameState,
+        deadline: Instant,
+        evaluator: &'a E,
+    ) -> Self {
+        Self {
+            config,
+            root,
+            deadline,
+            timed_out: false,
+            you: root.you.id,
+            evaluator,
+            zobrist: Zobrist::new(root),
+            hash: 0,
+            table: TranspositionTable::new(config.table_size),
+            nodes: 0,
+        }
+    }
+
+    /// Runs the search one turn deeper at a time, until it runs out of time or reaches the maximum
+    /// depth. Returns the best move for us from the deepest search that finished, or `None` if the game
+    /// is already over.
+    pub fn search(&mut self) -> Option<Move> {
+        let mut state = self.root.clone();
+        let mut best = None;
+        self.hash = self.zobrist.hash(&state);
+
+        for depth in 1..=self.config.depth {
+            // The best move from the last search is searched first, as it is most likely to still be the
+            // best move, which lets alpha-beta prune more.
+            let (value, move_) = self.max(&mut state, depth, LOSS * 2.0, WIN * 2.0, best);
+
+            // If we ran out of time, this search didn't finish, so we can't trust its result.
+            if self.timed_out {
+                debug!("Minimax: ran out of time at depth {depth}");
+                break;
+            }
+
+            debug!(
+                "Minimax: searched {} states at depth {depth}, best move is {move_:?} with a value of {value:.3}",
+                self.nodes
+            );
+
+            best = move_;
+
+            // If the game is decided either way, searching deeper won't change anything.
+            if value.abs() >= WIN {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+/// Private API for minimax.
+impl<'a, E: Evaluator + ?Sized> Minimax<'a, E> {
+    /// The value of the state for us when it's our turn to move, and the move that gets us that value. If
+    /// `first` is given, that move is searched before any others.
+    fn max(
+        &mut self,
+        state: &mut GameState,
+        depth: u32,
+        mut alpha: f64,
+        mut beta: f64,
+        mut first: Option<Move>,
+    ) -> (f64, Option<Move>) {
+        self.nodes += 1;
+
+        if self.timed_out || Instant::now() >= self.deadline {
+            self.timed_out = true;
+            return (0.0, None);
+        }
+
+        if depth == 0 || !state.is_alive(&self.you) || state.is_over() {
+            return (self.value(state, &Grid::new(&state.board), depth), None);
+        }
+
+        let hash = self.hash;
+        let original_alpha = alpha;
+
+        // If we have already searched this state deep enough, we might already know its value, or at
+        // least be able to narrow down the window we are searching in. Either way, its best move is
+        // a good one to search first.
+        if let Some(entry) = self.table.get(hash) {
+            if entry.depth >= depth {
+                match entry.bound {
+                    Bound::Exact => return (entry.value, entry.best),
+                    Bound::Lower => alpha = alpha.max(entry.value),
+                    Bound::Upper => beta = beta.min(entry.value),
+                }
+
+                if alpha >= beta {
+                    return (entry.value, entry.best);
+                }
+            }
+
+            first = first.or(entry.best);
+        }
+
+        let mut best = (LOSS * 2.0, None);
+        // Every snake's safe moves are looked up on the same grid, which is only built once for this state.
+        let grid = Grid::new(&state.board);
+        let mut moves = self.ordered_moves(&grid);
+
+        if let Some(first) = first.and_then(|first| moves.iter().position(|m| *m == first)) {
+            let first = moves.remove(first);
+            moves.insert(0, first);
+        }
+
+        for move_ in moves {
+            let value = self.min(state, &grid, move_, depth, alpha, beta);
+
+            if value > best.0 {
+                best = (value, Some(move_));
+            }
+
+            alpha = alpha.max(value);
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        // Results of searches that ran out of time are incomplete, so they can't be remembered.
+        if !self.timed_out {
+            let bound = if best.0 <= original_alpha {
+                Bound::Upper
+            } else if best.0 >= beta {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+
+            self.table.insert(Entry {
+                hash,
+                depth,
+                value: best.0,
+                bound,
+                best: best.1,
+            });
+        }
+
+        best
+    }
+
+    /// The value of the state (whose grid is `grid`) for us when we have made `move_`, and it's every other
+    /// snake's turn to move. The other snakes pick whichever combination of moves is worst for us.
+    fn min(
+        &mut self,
+        state: &mut GameState,
+        grid: &Grid,
+        move_: Move,
+        depth: u32,
+        alpha: f64,
+        mut beta: f64,
+    ) -> f64 {
+        let mut worst = WIN * 2.0;
+
+        for mut moves in self.ordered_replies(state, grid, move_) {
+            moves.insert(self.you, move_);
+
+            let delta = self.apply(state, &moves);
+            let (value, _) = self.max(state, depth - 1, alpha, beta, None);
+            self.undo(state, delta);
+
+            worst = worst.min(value);
+            beta = beta.min(value);
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        worst
+    }
+
+    /// Applies a turn to the state, and XORs the keys that changed during it into the hash of the state.
+    fn apply(&mut self, state: &mut GameState, moves: &HashMap<SnakeID, Move>) -> TurnDelta {
+        let mut delta = state.apply(moves);
+        self.zobrist.apply(state, &mut delta);
+        self.hash ^= delta.hash;
+
+        delta
+    }
+
+    /// Undoes a turn applied with `apply`, and XORs the keys that changed during it back out of the hash.
+    fn undo(&mut self, state: &mut GameState, delta: TurnDelta) {
+        self.hash ^= delta.hash;
+        state.undo(delta);
+    }
+
+    /// The value of a state (whose grid is `grid`) at the end of the search. Finished games are worth a lot
+    /// more than anything the evaluation function can return, and winning sooner (or losing later) is better.
+    fn value(&self, state: &GameState, grid: &Grid, depth: u32) -> f64 {
+        if !state.is_alive(&self.you) {
+            LOSS - depth as f64
+        } else if state.is_over() {
+            WIN + depth as f64
+        } else {
+            self.evaluator.evaluate(state, grid, &self.you)
+        }
+    }
+
+    /// Our moves on the state's grid, ordered from most to least promising so that alpha-beta can prune as
+    /// much as possible. A move is more promising the more of the board we get to first after making it (with
+    /// every other snake staying where it is), which is worked out on the grid we already have, instead of
+    /// building a new one for every move.
+    fn ordered_moves(&self, grid: &Grid) -> Vec<Move> {
+        let Some(slot) = grid.slot(&self.you) else {
+            return Move::all().to_vec();
+        };
+        let mut moves = grid.safe_moves(slot);
+
+        // If we have no safe moves, we are going to die anyways, so just pick any move.
+        if moves.is_empty() {
+            moves = Move::all().to_vec();
+        }
+
+        let mut heads = grid
+            .snakes()
+            .iter()
+            .map(|snake| snake.head())
+            .collect::<Vec<_>>();
+        let mut scored = moves
+            .into_iter()
+            .map(|move_| {
+                heads[slot] = grid.move_point(&grid.snakes()[slot].head(), move_);
+                let territory = grid
+                    .voronoi(&heads)
+                    .values()
+                    .filter(|(owner, _)| *owner == slot)
+                    .count();
+
+                (move_, territory)
+            })
+            .collect::<Vec<_>>();
+
+        // This is a stable sort, so moves that are just as good keep the order of `Move::all()`.
+        scored.sort_by(|(_, a), (_, b)| b.cmp(a));
+        scored.into_iter().map(|(move_, _)| move_).collect()
+    }
+
+    /// Every combination of moves the other snakes can make in response to our move, ordered from most
+    /// to least dangerous. A combination is more dangerous the closer it brings the other snakes' heads
+    /// to where our head will be.
+    fn ordered_replies(
+        &self,
+        state: &GameState,
+        grid: &Grid,
+        move_: Move,
+    ) -> Vec<HashMap<SnakeID, Move>> {
+        let Some(you) = state.board.snakes.get(&self.you) else {
+            return Vec::new();
+        };
+        let head = state.board.move_point(&you.head, move_);
+
+        let others = grid
+            .snakes()
+            .iter()
+            .enumerate()
+            .filter(|(_, snake)| snake.id != self.you)
+            .map(|(slot, snake)| {
+                let mut moves = grid.safe_moves(slot);
+
+                // A snake with no safe moves is going to die no matter what it does.
+                if moves.is_empty() {
+                    moves = vec![state.board.default_move(&state.board.snakes[&snake.id])];
+                }
+
+                moves
+                    .into_iter()
+                    .map(|move_| {
+                        (
+                            snake.id,
+                            move_,
+                            state
+                                .board
+                                .distance(&grid.move_point(&snake.head(), move_), &head),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // If there are no other snakes, there is exactly one way for them to respond: by doing nothing.
+        if others.is_empty() {
+            return vec![HashMap::new()];
+        }
+
+        others
+            .into_iter()
+            .multi_cartesian_product()
+            .sorted_by_key(|replies| replies.iter().map(|(_, _, distance)| distance).sum::<u32>())
+            .map(|replies| {
+                replies
+                    .into_iter()
+                    .map(|(id, move_, _)| (id, move_))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::evaluation::WeightedEvaluator, objects::testing::TestState};
+    use std::time::Duration;
+
+    /// We are on the left edge of the board with our body below us, so moving left or down kills us.
+    fn cornered() -> GameState {
+        TestState::new(11, 11)
+            .snake('a', 90, &[(0, 5), (0, 4), (0, 3)])
+            .snake('b', 90, &[(8, 5), (8, 4), (8, 3)])
+            .build()
+    }
+
+    #[test]
+    fn lethal_moves_are_avoided() {
+        let state = cornered();
+        let config = MinimaxConfig {
+            depth: 3,
+            ..MinimaxConfig::default()
+        };
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        let move_ = Minimax::new(config, &state, deadline, &WeightedEvaluator::default()).search();
+
+        assert!(matches!(move_, Some(Move::Up | Move::Right)), "{move_:?}");
+    }
+
+    #[test]
+    fn the_search_stops_at_the_deadline() {
+        let state = cornered();
+        let start = Instant::now();
+        let deadline = start + Duration::from_millis(50);
+
+        let move_ = Minimax::new(
+            MinimaxConfig::default(),
+            &state,
+            deadline,
+            &WeightedEvaluator::default(),
+        )
+        .search();
+
+        // The search can only stop between steps, so it may run a little over.
+        assert!(move_.is_some());
+        assert!(
+            start.elapsed() < Duration::from_millis(250),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+pub mod duct;
+pub mod mcts;
+pub mod minimax;
+pub mod table;
+pub mod zobrist;
+
+use crate::{
+    configuration::mcts::MCTSConfig,
+    objects::{grid::Grid, moves::Move, snake::SnakeID, GameState},
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// The statistics of a move in a search tree.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Stats {
+    /// How many times the move has been made.
+    pub visits: u32,
+
+    /// The total reward of every game the move was made in.
+    pub value: f64,
+}
+
+impl Stats {
+    /// Records the reward of a game the move was made in.
+    pub fn update(&mut self, reward: f64) {
+        self.visits += 1;
+        self.value += reward;
+    }
+
+    /// The average reward of every game the move was made in.
+    pub fn mean(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.value / self.visits as f64
+        }
+    }
+
+    /// The UCT score of the move: its average reward, plus a bonus for how rarely it has been made compared to
+    /// its parent. Moves that have never been made are scored as infinitely good, so that they are always tried
+    /// first.
+    pub fn uct(&self, parent_visits: f64, exploration: f64) -> f64 {
+        if self.visits == 0 {
+            f64::INFINITY
+        } else {
+            self.mean() + exploration * (parent_visits.ln() / self.visits as f64).sqrt()
+        }
+    }
+}
+
+/// Returns true if the game should still be simulated: `you` is alive, someone else is still alive, and the game
+/// hasn't been searched `max_depth` turns deep yet.
+pub fn is_running(state: &GameState, you: &SnakeID, depth: u32, max_depth: u32) -> bool {
+    depth < max_depth && state.is_alive(you) && !state.is_over()
+}
+
+/// Plays out `games_per_search` random games in parallel from the given state, which is `depth` turns into the
+/// search, until they stop running. Returns the reward of every game, as worked out by `reward` from the state
+/// the game stopped in and its grid.
+pub fn play_out<T, F>(
+    config: &MCTSConfig,
+    state: &GameState,
+    you: &SnakeID,
+    depth: u32,
+    reward: F,
+) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&GameState, &Grid) -> T + Sync,
+{
+    (0..config.games_per_search.max(1))
+        .into_par_iter()
+        .map(|_| {
+            let mut rng = SmallRng::from_entropy();
+            let mut state = state.clone();
+            let mut depth = depth;
+
+            loop {
+                // The grid of every state is used to pick the moves from it, or to work out the reward once
+                // the game stops.
+                let grid = Grid::new(&state.board);
+
+                if !is_running(&state, you, depth, config.max_depth) {
+                    return reward(&state, &grid);
+                }
+
+                state.advance(&random_moves(&grid, &mut rng));
+                depth += 1;
+            }
+        })
+        .collect()
+}
+
+/// Turns an evaluation of a game that hasn't been decided yet into a reward between 0 and 1, where an even
+/// game (an evaluation of 0) is worth 0.5, like a draw.
+pub fn squash(value: f64) -> f64 {
+    1.0 / (1.0 + (-value).exp())
+}
+
+/// Picks a random safe move for every snake on the grid, or a completely random move if a snake has no
+/// safe moves left. Used to play out games during searches.
+pub fn random_moves<R: Rng>(grid: &Grid, rng: &mut R) -> HashMap<SnakeID, Move> {
+    grid.snakes()
+        .iter()
+        .enumerate()
+        .map(|(slot, snake)| {
+            let move_ = grid
+                .safe_moves(slot)
+                .choose(rng)
+                .copied()
+                .unwrap_or_else(|| Move::random(rng));
+
+            (snake.id, move_)
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+// A transposition table stores the results of searches of states we have already seen, keyed by their Zobrist hash. The
+// same state can be reached by making moves in different orders, so this saves us from searching it more than once. The
+// table has a fixed size, so it can't grow without bound during a search. Only minimax uses it: its entries hold bounds
+// from alpha-beta pruning, which MCTS and DUCT have no use for, as they keep their statistics in their own trees.
+
+use crate::objects::moves::Move;
+use std::mem::size_of;
+
+/// How the value stored in an entry relates to the real value of the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// The value is exactly the value of the state.
+    Exact,
+
+    /// The real value of the state is at least the stored value.
+    Lower,
+
+    /// The real value of the state is at most the stored value.
+    Upper,
+}
+
+/// The result of searching a state.
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    /// The full hash of the state, to tell apart states that map to the same slot in the table.
+    pub hash: u64,
+
+    /// How many turns deep the state was searched.
+    pub depth: u32,
+
+    /// The value of the state.
+    pub value: f64,
+
+    /// How `value` relates to the real value of the state.
+    pub bound: Bound,
+
+    /// The best move found for the state, if any.
+    pub best: Option<Move>,
+}
+
+/// A fixed-size transposition table.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    /// The entries of the table.
+    entries: Vec<Option<Entry>>,
+}
+
+/// Public API for the transposition table.
+impl TranspositionTable {
+    /// Creates a new table that uses (about) the given number of megabytes of memory.
+    pub fn new(megabytes: u32) -> Self {
+        let size = (megabytes as usize * 1024 * 1024 / size_of::<Option<Entry>>()).max(1);
+
+        Self {
+            entries: vec![None; size],
+        }
+    }
+
+    /// Gets the entry for the state with the given hash, if it's in the table.
+    pub fn get(&self, hash: u64) -> Option<&Entry> {
+        self.entries[self.index(hash)]
+            .as_ref()
+            .filter(|entry| entry.hash == hash)
+    }
+
+    /// Stores an entry in the table. If another entry is already in its slot, the entry searched deeper
+    /// is kept, preferring the new entry if they were searched equally deep.
+    pub fn insert(&mut self, entry: Entry) {
+        let index = self.index(entry.hash);
+
+        match &self.entries[index] {
+            Some(old) if old.depth > entry.depth && old.hash != entry.hash => {}
+            _ => self.entries[index] = Some(entry),
+        }
+    }
+}
+
+/// Private API for the transposition table.
+impl TranspositionTable {
+    /// Gets the slot in the table for the given hash.
+    fn index(&self, hash: u64) -> usize {
+        (hash % self.entries.len() as u64) as usize
+    }
+}
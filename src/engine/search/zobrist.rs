@@ -0,0 +1,347 @@
+// Zobrist hashing lets us quickly get a (practically) unique hash for every state we search. Every feature of the board
+// (a piece of food on a cell, a snake's body on a cell, a snake's health, ...) gets its own random key, and the hash of
+// a state is just all the keys of its features XOR'd together. This is a lot faster than hashing all the `HashMap`s
+// and `HashSet`s that make up a `Board`.
+
+use crate::objects::{
+    point::Point,
+    simulator::{MovedSnake, TurnDelta},
+    snake::{Snake, SnakeID},
+    GameState,
+};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::collections::HashMap;
+
+/// The number of buckets a snake's health is split into. Snakes with health in the same bucket are
+/// hashed the same, as small differences in health rarely matter.
+const HEALTH_BUCKETS: usize = 11;
+
+/// The maximum length of a snake that gets its own key. Longer snakes all share the same key.
+const MAX_LENGTH: usize = 256;
+
+/// The keys for Zobrist hashing states on a board of a certain size, with a certain set of snakes.
+#[derive(Debug, Clone)]
+pub struct Zobrist {
+    /// The width of the board.
+    width: u32,
+
+    /// The height of the board.
+    height: u32,
+
+    /// The "slot" every snake's keys are stored in.
+    slots: HashMap<SnakeID, usize>,
+
+    /// The keys for food on every cell.
+    food: Vec<u64>,
+
+    /// The keys for hazards on every cell.
+    hazards: Vec<u64>,
+
+    /// The keys for every snake's body on every cell.
+    bodies: Vec<Vec<u64>>,
+
+    /// The keys for every snake's head on every cell.
+    heads: Vec<Vec<u64>>,
+
+    /// The keys for every snake's tail on every cell.
+    tails: Vec<Vec<u64>>,
+
+    /// The keys for every snake's length.
+    lengths: Vec<Vec<u64>>,
+
+    /// The keys for every snake's health bucket.
+    health: Vec<Vec<u64>>,
+}
+
+/// Public API for Zobrist hashing.
+impl Zobrist {
+    /// Creates the keys for hashing states that come from the given state. The keys are always generated
+    /// from the same seed, so the same board gets the same keys every time.
+    pub fn new(state: &GameState) -> Self {
+        let mut rng = SmallRng::seed_from_u64(0x6865_6d61_7469_7465);
+        let (width, height) = (state.board.width, state.board.height);
+        let cells = (width * height) as usize;
+
+        let mut ids = state.board.snakes.keys().copied().collect::<Vec<_>>();
+        ids.sort_by_key(|id| id.to_string());
+        let slots = ids
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| (id, i))
+            .collect::<HashMap<_, _>>();
+
+        let mut keys = |n: usize| (0..n).map(|_| rng.gen()).collect::<Vec<u64>>();
+        let snakes = slots.len();
+
+        Self {
+            width,
+            height,
+            food: keys(cells),
+            hazards: keys(cells),
+            bodies: (0..snakes).map(|_| keys(cells)).collect(),
+            heads: (0..snakes).map(|_| keys(cells)).collect(),
+            tails: (0..snakes).map(|_| keys(cells)).collect(),
+            lengths: (0..snakes).map(|_| keys(MAX_LENGTH)).collect(),
+            health: (0..snakes).map(|_| keys(HEALTH_BUCKETS)).collect(),
+            slots,
+        }
+    }
+
+    /// Hashes the given state from scratch. Searches only need to do this once, for the state they start from,
+    /// and can then keep the hash up to date with `apply` as they play turns.
+    pub fn hash(&self, state: &GameState) -> u64 {
+        let board = &state.board;
+        let mut hash = 0;
+
+        for food in &board.food {
+            hash ^= self.cell_key(&self.food, food);
+        }
+
+        for hazard in board.hazards.keys() {
+            hash ^= self.cell_key(&self.hazards, hazard);
+        }
+
+        for snake in board.snakes.values() {
+            if let Some(&slot) = self.slots.get(&snake.id) {
+                hash ^= self.snake_keys(slot, snake.body.iter(), snake.length, snake.health);
+            }
+        }
+
+        hash
+    }
+
+    /// Works out which keys changed during a turn that was just applied to `state`, and stores them in the turn's
+    /// delta. XOR'ing `delta.hash` into the hash of the state before the turn gives the hash of the state after
+    /// it, and XOR'ing it in again gives the hash from before. Hazards never change during a turn, and every
+    /// snake that survived only moved its head and tail, so this only looks at what actually changed.
+    pub fn apply(&self, state: &GameState, delta: &mut TurnDelta) {
+        let mut hash = 0;
+
+        for food in delta.eaten() {
+            hash ^= self.cell_key(&self.food, food);
+        }
+
+        for moved in delta.moved() {
+            let Some(&slot) = self.slots.get(&moved.id) else {
+                continue;
+            };
+
+            hash ^= match state.board.snakes.get(&moved.id) {
+                Some(snake) => self.moved_keys(slot, snake, moved),
+                None => {
+                    let snake = delta
+                        .eliminated_snakes()
+                        .iter()
+                        .find(|snake| snake.id == moved.id)
+                        .expect("Every snake that moved should be on the board or eliminated.");
+
+                    self.eliminated_keys(slot, snake, moved)
+                }
+            };
+        }
+
+        delta.hash = hash;
+    }
+}
+
+/// Private API for Zobrist hashing.
+impl Zobrist {
+    /// Gets the key for the given cell out of a set of keys for every cell. Points that aren't on the
+    /// board don't have a key.
+    fn cell_key(&self, keys: &[u64], point: &Point) -> u64 {
+        if point.x < 0
+            || point.y < 0
+            || point.x >= self.width as i32
+            || point.y >= self.height as i32
+        {
+            return 0;
+        }
+
+        keys[(point.y as u32 * self.width + point.x as u32) as usize]
+    }
+
+    /// Gets all the keys of a snake in the given slot with the given body (from head to tail), length and health.
+    fn snake_keys<'a>(
+        &self,
+        slot: usize,
+        body: impl Iterator<Item = &'a Point>,
+        length: u32,
+        health: i32,
+    ) -> u64 {
+        let mut hash = self.length_key(slot, length) ^ self.health_key(slot, health);
+        let mut previous = None;
+
+        for cell in body {
+            if previous.is_none() {
+                hash ^= self.cell_key(&self.heads[slot], cell);
+            }
+
+            // Stacked segments would cancel each other out, so every cell is only hashed once. The length of the
+            // snake covers any stacked segments instead. Segments can only be stacked on the tail, so they are
+            // always next to each other.
+            if previous != Some(cell) {
+                hash ^= self.cell_key(&self.bodies[slot], cell);
+            }
+
+            previous = Some(cell);
+        }
+
+        if let Some(tail) = previous {
+            hash ^= self.cell_key(&self.tails[slot], tail);
+        }
+
+        hash
+    }
+
+    /// Gets the keys that changed for a snake that survived a turn. Its new head was pushed onto its body and its
+    /// old tail was popped off (unless another segment was stacked on it), and its length and health changed.
+    fn moved_keys(&self, slot: usize, snake: &Snake, moved: &MovedSnake) -> u64 {
+        let (head, length) = Self::previous_head_and_length(snake, moved);
+        // The segments the snake had before the turn are still there, after its new head.
+        let kept = &snake.body[..(length as usize).min(snake.body.len())];
+        let mut hash = 0;
+
+        // A snake that survived can't have moved into its own body, except into the cell its tail just left, in
+        // which case the cell is still covered. Otherwise, the old tail's cell is only left empty if there wasn't
+        // another segment stacked on it.
+        if snake.head != moved.tail {
+            hash ^= self.cell_key(&self.bodies[slot], &snake.head);
+
+            if kept.last() != Some(&moved.tail) {
+                hash ^= self.cell_key(&self.bodies[slot], &moved.tail);
+            }
+        }
+
+        hash ^ self.cell_key(&self.heads[slot], &head)
+            ^ self.cell_key(&self.heads[slot], &snake.head)
+            ^ self.cell_key(&self.tails[slot], &moved.tail)
+            ^ self.cell_key(&self.tails[slot], &snake.tail())
+            ^ self.length_key(slot, length)
+            ^ self.length_key(slot, snake.length)
+            ^ self.health_key(slot, moved.health)
+            ^ self.health_key(slot, snake.health)
+    }
+
+    /// Gets all the keys a snake that was eliminated during a turn had before the turn.
+    fn eliminated_keys(&self, slot: usize, snake: &Snake, moved: &MovedSnake) -> u64 {
+        let (_, length) = Self::previous_head_and_length(snake, moved);
+        // The snake's body before the turn is its body after the turn without its new head or any growth, and
+        // with its old tail back on.
+        let end = (length as usize).min(snake.body.len());
+        let body = snake.body[1..end].iter().chain([&moved.tail]);
+
+        self.snake_keys(slot, body, length, moved.health)
+    }
+
+    /// Gets the head and length a snake had before a turn, from the snake after the turn.
+    fn previous_head_and_length(snake: &Snake, moved: &MovedSnake) -> (Point, u32) {
+        let length = snake.length - moved.growth;
+        let head = if length > 1 {
+            snake.body[1]
+        } else {
+            moved.tail
+        };
+
+        (head, length)
+    }
+
+    /// Gets the key for a snake's length.
+    fn length_key(&self, slot: usize, length: u32) -> u64 {
+        self.lengths[slot][(length as usize).min(MAX_LENGTH - 1)]
+    }
+
+    /// Gets the key for a snake's health.
+    fn health_key(&self, slot: usize, health: i32) -> u64 {
+        self.health[slot][Self::health_bucket(health)]
+    }
+
+    /// Gets the health bucket for the given amount of health.
+    fn health_bucket(health: i32) -> usize {
+        (health.clamp(0, 100) as usize * (HEALTH_BUCKETS - 1)) / 100
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::{moves::Move, settings::GameType, testing::TestState};
+    use rand::seq::SliceRandom;
+
+    /// A crowded board, where snakes eat, starve, collide and leave the board within a few turns.
+    fn crowded(game_type: GameType) -> GameState {
+        TestState::new(7, 7)
+            .game_type(game_type)
+            .squad_rules(true, true, true, true)
+            .squad_snake('a', "red", 12, &[(1, 1), (1, 2), (1, 3)])
+            .squad_snake('b', "red", 60, &[(5, 5), (5, 4), (5, 3), (5, 3)])
+            .squad_snake('c', "blue", 30, &[(3, 3), (3, 3), (3, 3)])
+            .squad_snake('d', "blue", 3, &[(1, 5), (2, 5), (3, 5), (4, 5), (4, 6)])
+            .food(&[(2, 2), (4, 4), (0, 0), (6, 6), (3, 1), (2, 4)])
+            .hazards(&[(0, 3), (1, 0), (1, 0)])
+            .build()
+    }
+
+    #[test]
+    fn applying_turns_keeps_the_hash_up_to_date() {
+        let mut rng = SmallRng::seed_from_u64(6);
+
+        for game_type in GameType::ALL {
+            for _ in 0..100 {
+                let mut state = crowded(game_type);
+                let zobrist = Zobrist::new(&state);
+                let mut hash = zobrist.hash(&state);
+                let mut turns = Vec::new();
+
+                while !state.board.snakes.is_empty() && turns.len() < 12 {
+                    let moves = state
+                        .board
+                        .snakes
+                        .keys()
+                        .map(|id| {
+                            let safe = state.safe_moves(id);
+                            let move_ = safe
+                                .choose(&mut rng)
+                                .copied()
+                                .filter(|_| rng.gen_bool(0.8))
+                                .unwrap_or_else(|| Move::random(&mut rng));
+                            (*id, move_)
+                        })
+                        .collect();
+
+                    let mut delta = state.apply(&moves);
+                    zobrist.apply(&state, &mut delta);
+                    hash ^= delta.hash;
+
+                    assert_eq!(hash, zobrist.hash(&state), "{game_type:?}: {state:?}");
+                    turns.push((hash, delta));
+                }
+
+                while let Some((after, delta)) = turns.pop() {
+                    assert_eq!(hash, after);
+                    hash ^= delta.hash;
+                    state.undo(delta);
+
+                    assert_eq!(hash, zobrist.hash(&state), "{game_type:?}: {state:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stacked_segments_are_hashed_once() {
+        let state = TestState::new(7, 7)
+            .snake('a', 50, &[(3, 3), (3, 3), (3, 3)])
+            .build();
+        let zobrist = Zobrist::new(&state);
+        let slot = zobrist.slots[&state.you.id];
+
+        assert_eq!(
+            zobrist.hash(&state),
+            zobrist.cell_key(&zobrist.bodies[slot], &Point::new(3, 3))
+                ^ zobrist.cell_key(&zobrist.heads[slot], &Point::new(3, 3))
+                ^ zobrist.cell_key(&zobrist.tails[slot], &Point::new(3, 3))
+                ^ zobrist.length_key(slot, 3)
+                ^ zobrist.health_key(slot, 50)
+        );
+    }
+}
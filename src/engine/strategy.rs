@@ -0,0 +1,229 @@
+// Strategies decide what the snake does. Every turn, the engine asks its registry of strategies which one applies
+// to the current state, and then asks that strategy to score the moves it would make. The built-in strategies are
+// all here, but anything that implements `Strategy` can be registered, so new strategies can be added (or swapped
+// out, or tried against each other) without touching the engine itself.
+
+use super::Engine;
+use crate::objects::{moves::Move, settings::GameType};
+use std::{fmt::Debug, sync::Arc};
+
+/// A way of picking moves. Strategies only get a read-only view of the engine, so they can be shared between
+/// games and threads.
+pub trait Strategy: Debug + Send + Sync {
+    /// The name of the strategy, for logging.
+    fn name(&self) -> &'static str;
+
+    /// Returns true if the strategy should be used for the engine's current state.
+    fn applies(&self, engine: &Engine) -> bool;
+
+    /// Scores every move the strategy would consider making for the engine's current state, where higher is
+    /// better. Moves that are left out are never made. Returns nothing if the strategy can't come up with a move.
+    fn score_moves(&self, engine: &Engine) -> Vec<(Move, f64)>;
+
+    /// Proposes a move for the engine's current state, or None if the strategy can't come up with one. By
+    /// default, this is the move with the highest score (the first one, if there's a tie).
+    fn propose(&self, engine: &Engine) -> Option<Move> {
+        self.score_moves(engine)
+            .into_iter()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(move_, _)| move_)
+    }
+}
+
+/// The scores of a strategy that only ever has one move in mind: that move, with a score of 1.
+fn only(move_: Option<Move>) -> Vec<(Move, f64)> {
+    move_.map(|move_| vec![(move_, 1.0)]).unwrap_or_default()
+}
+
+/// An ordered list of strategies. The first strategy that applies to a state is the one that gets used.
+#[derive(Debug, Clone)]
+pub struct StrategyRegistry {
+    /// The strategies, in order of priority.
+    strategies: Vec<Arc<dyn Strategy>>,
+}
+
+impl StrategyRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            strategies: Vec::new(),
+        }
+    }
+
+    /// Adds a strategy to the end of the registry, so it's only used if none of the strategies before it apply.
+    pub fn register(mut self, strategy: impl Strategy + 'static) -> Self {
+        self.strategies.push(Arc::new(strategy));
+        self
+    }
+
+    /// Adds a strategy at the given position in the registry, ahead of every strategy after it.
+    pub fn insert(mut self, index: usize, strategy: impl Strategy + 'static) -> Self {
+        self.strategies.insert(index, Arc::new(strategy));
+        self
+    }
+
+    /// Returns the first strategy that applies to the engine's current state, if any.
+    pub fn select(&self, engine: &Engine) -> Option<Arc<dyn Strategy>> {
+        self.strategies
+            .iter()
+            .find(|strategy| strategy.applies(engine))
+            .cloned()
+    }
+}
+
+impl Default for StrategyRegistry {
+    /// The built-in strategies, in the order the engine has always picked them in.
+    fn default() -> Self {
+        Self::new()
+            .register(Constrictor)
+            .register(Hungry)
+            .register(Survival)
+            .register(Aggressive)
+            .register(Searching)
+    }
+}
+
+/// In constrictor games, keeps as much space to ourselves as possible. See `Engine::constrictor_move`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Constrictor;
+
+impl Strategy for Constrictor {
+    fn name(&self) -> &'static str {
+        "constrictor"
+    }
+
+    fn applies(&self, engine: &Engine) -> bool {
+        engine.state.game.ruleset.name == GameType::Constrictor
+    }
+
+    fn score_moves(&self, engine: &Engine) -> Vec<(Move, f64)> {
+        only(engine.constrictor_move())
+    }
+}
+
+/// Goes for food when we're hungry. See `Engine::hungry_move`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hungry;
+
+impl Strategy for Hungry {
+    fn name(&self) -> &'static str {
+        "hungry"
+    }
+
+    fn applies(&self, engine: &Engine) -> bool {
+        engine.is_hungry()
+    }
+
+    fn score_moves(&self, engine: &Engine) -> Vec<(Move, f64)> {
+        only(engine.hungry_move())
+    }
+}
+
+/// Follows our tail when we need food but can't get any, or there's nobody left to play against. See
+/// `Engine::survival_move`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Survival;
+
+impl Strategy for Survival {
+    fn name(&self) -> &'static str {
+        "survival"
+    }
+
+    fn applies(&self, engine: &Engine) -> bool {
+        engine.needs_food() || engine.enemy_snakes().next().is_none()
+    }
+
+    fn score_moves(&self, engine: &Engine) -> Vec<(Move, f64)> {
+        only(engine.survival_move())
+    }
+}
+
+/// Hunts shorter snakes when we're comfortably the longest, and searches when there's nobody to hunt. See
+/// `Engine::aggressive_move`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Aggressive;
+
+impl Strategy for Aggressive {
+    fn name(&self) -> &'static str {
+        "aggressive"
+    }
+
+    fn applies(&self, engine: &Engine) -> bool {
+        engine.is_aggressive()
+    }
+
+    fn score_moves(&self, engine: &Engine) -> Vec<(Move, f64)> {
+        only(engine.aggressive_move().or_else(|| engine.searching_move()))
+    }
+}
+
+/// Searches through possible futures of the game with the configured search algorithm. See
+/// `Engine::searching_move`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Searching;
+
+impl Strategy for Searching {
+    fn name(&self) -> &'static str {
+        "searching"
+    }
+
+    fn applies(&self, _engine: &Engine) -> bool {
+        true
+    }
+
+    fn score_moves(&self, engine: &Engine) -> Vec<(Move, f64)> {
+        only(engine.searching_move())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{configuration::engine::EngineConfig, objects::testing::TestState};
+
+    /// A strategy that always scores the same moves.
+    #[derive(Debug, Clone)]
+    struct Fixed(Vec<(Move, f64)>);
+
+    impl Strategy for Fixed {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn applies(&self, _engine: &Engine) -> bool {
+            true
+        }
+
+        fn score_moves(&self, _engine: &Engine) -> Vec<(Move, f64)> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn the_engine_makes_the_best_scored_move() {
+        let state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .build();
+        let strategy = Fixed(vec![(Move::Up, 0.5), (Move::Left, 2.0), (Move::Right, 2.0)]);
+        let mut engine = Engine::with_strategies(
+            EngineConfig::default(),
+            state.clone(),
+            StrategyRegistry::new().register(strategy.clone()),
+        );
+        engine.update(state);
+
+        assert_eq!(strategy.propose(&engine), Some(Move::Left));
+        assert_eq!(engine.get_move(), Move::Left);
+    }
+
+    #[test]
+    fn strategies_without_moves_propose_nothing() {
+        let state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .build();
+        let engine = Engine::new(EngineConfig::default(), state);
+
+        assert_eq!(Fixed(Vec::new()).propose(&engine), None);
+    }
+}
@@ -1,69 +1,225 @@
 // This module deals with area-control-like algorithms and other things that have to do with areas
 
-use crate::{engine::Engine, objects::point::Point};
-use itertools::Itertools;
-use std::collections::HashSet;
+use crate::{
+    engine::Engine,
+    objects::{grid::Grid, moves::Move, point::Point, snake::SnakeID},
+};
+use std::collections::HashMap;
+
+/// Which snake controls which parts of the board. A snake controls a cell if it can get there before every
+/// other snake, or at the same time as only shorter snakes. Cells that equally long snakes get to at the same
+/// time, or that nobody can get to, belong to nobody.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Territory {
+    /// How many cells every snake controls.
+    counts: HashMap<SnakeID, u32>,
+
+    /// The snake that controls every cell, for every cell that is controlled by someone.
+    owners: HashMap<Point, SnakeID>,
+}
 
-impl Engine {
-    /// Returns the area the engine controls as a u32.
-    pub fn area_control(&self, head: &Point) -> u32 {
-        // The other heads of the snakes
-        let other_heads = self.other_snakes().map(|s| s.head).collect_vec();
+impl Territory {
+    /// Works out the territory on `grid`, with a breadth-first search from every snake's head at the same time.
+    /// `heads` has the head to search from for every slot. Every cell is owned by whoever gets there first, but
+    /// it's only counted for them if `counts` returns true for the owner, the cell and how many moves it takes
+    /// them to get there.
+    pub fn new(
+        grid: &Grid,
+        heads: &[Point],
+        counts: impl Fn(&SnakeID, &Point, u32) -> bool,
+    ) -> Self {
+        let snakes = grid.snakes();
+        let mut territory = Self::default();
+
+        for (point, (slot, distance)) in grid.voronoi(heads) {
+            let id = snakes[slot].id;
+            territory.owners.insert(point, id);
+
+            if counts(&id, &point, distance) {
+                *territory.counts.entry(id).or_default() += 1;
+            }
+        }
 
-        let mut area = 0;
-        let mut queue = Vec::new();
-        let mut visited = HashSet::new();
+        territory
+    }
 
-        queue.push(*head);
+    /// Returns how many cells the snake with the given ID controls.
+    pub fn count(&self, id: &SnakeID) -> u32 {
+        self.counts.get(id).copied().unwrap_or(0)
+    }
 
-        while let Some(point) = queue.pop() {
-            if visited.contains(&point) {
-                continue;
-            }
+    /// Returns the snake that controls the given cell, if any.
+    pub fn owner(&self, point: &Point) -> Option<SnakeID> {
+        self.owners.get(point).copied()
+    }
 
-            visited.insert(point);
+    /// Returns every cell the snake with the given ID controls.
+    pub fn owned<'a>(&'a self, id: &'a SnakeID) -> impl Iterator<Item = &'a Point> + 'a {
+        self.owners
+            .iter()
+            .filter(move |(_, owner)| *owner == id)
+            .map(|(point, _)| point)
+    }
+}
 
-            let snake_distance = point.distance(head);
-            let other_distance = point.closest_distance(&other_heads).unwrap_or(u32::MAX);
+/// How much of the board we can get to from a cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reach {
+    /// How many cells we can get to, including the one we start on.
+    pub area: u32,
 
-            if snake_distance < other_distance {
-                area += 1;
-            }
+    /// Whether we can get to our own tail. If we can, we can always follow it around, so we aren't trapped
+    /// no matter how small the area is.
+    pub tail: bool,
+}
 
-            for neighbor in point.neighbors() {
-                if self.is_on_board(&neighbor) && !visited.contains(&neighbor) {
-                    queue.push(neighbor);
-                }
-            }
-        }
+impl Engine {
+    /// Works out which snake controls which parts of the board, with a breadth-first search from every
+    /// snake's head at the same time. Bodies block the search until they have moved out of the way, and
+    /// cells that might be hazards by the time we get to them aren't counted as ours.
+    pub fn territory(&self) -> Territory {
+        self.territory_with(self.head())
+    }
 
-        area
+    /// Works out the territory like `territory`, but with our head at `head`.
+    pub fn territory_with(&self, head: &Point) -> Territory {
+        let you = self.state.you.id;
+        let heads = self
+            .grid
+            .snakes()
+            .iter()
+            .map(|snake| if snake.id == you { *head } else { snake.head() })
+            .collect::<Vec<_>>();
+
+        Territory::new(&self.grid, &heads, |id, point, distance| {
+            *id != you || !self.will_be_hazard(point, distance)
+        })
     }
 
-    /// Returns the area the engine can access as a u32.
-    pub fn area_accessible(&self, head: &Point) -> u32 {
-        let mut area = 0;
-        let mut queue = Vec::new();
-        let mut visited = HashSet::new();
+    /// Returns how many cells we would control if our head were at `head`. See `territory`.
+    pub fn area_control(&self, head: &Point) -> u32 {
+        self.territory_with(head).count(&self.state.you.id)
+    }
 
-        queue.push(*head);
+    /// Flood fills the board from `start`, the cell our head is about to move into, and returns how much of
+    /// it we can get to. Bodies are walls, but only until they move out of the way: a segment can be moved
+    /// through if it will be gone by the time we get there. Squadmates we can move through aren't walls at all.
+    pub fn area_accessible(&self, start: &Point) -> Reach {
+        let Some(slot) = self.grid.slot(&self.state.you.id) else {
+            return Reach::default();
+        };
+        let reachable = self.grid.distances(slot, start, 1);
+
+        Reach {
+            area: reachable.len() as u32,
+            tail: reachable.contains_key(&self.state.you.tail()),
+        }
+    }
 
-        while let Some(point) = queue.pop() {
-            if visited.contains(&point) {
-                continue;
+    /// Returns how much of the board we can get to after making the given move. Unsafe moves (including ones
+    /// off the board) can't get anywhere.
+    pub fn reach(&self, move_: Move) -> Reach {
+        self.reaches[move_.index()]
+    }
+}
+
+/// Private API for areas.
+impl Engine {
+    /// Works out how much of the board we can get to after every move, in the order of `Move::all()`. Unsafe
+    /// moves don't get us anywhere.
+    pub(crate) fn measure_reaches(&self) -> [Reach; 4] {
+        let safe = self.safe_moves().collect::<Vec<_>>();
+
+        Move::all().map(|move_| {
+            if safe.contains(&move_) {
+                self.area_accessible(&self.state.board.move_point(self.head(), move_))
+            } else {
+                Reach::default()
             }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::engine::EngineConfig,
+        objects::{
+            settings::GameType,
+            testing::{id, TestState},
+        },
+    };
+
+    #[test]
+    fn territory_goes_to_whoever_gets_there_first() {
+        let state = TestState::new(7, 7)
+            .snake('a', 90, &[(1, 3), (1, 2), (1, 1)])
+            .snake('b', 90, &[(5, 3), (5, 4), (5, 5)])
+            .build();
+        let engine = Engine::new(EngineConfig::default(), state);
+        let territory = engine.territory();
+
+        assert_eq!(territory.owner(&Point::new(0, 3)), Some(id('a')));
+        assert_eq!(territory.owner(&Point::new(6, 3)), Some(id('b')));
+        assert_eq!(territory.owner(&Point::new(3, 3)), None);
+        assert_eq!(territory.count(&id('a')), territory.count(&id('b')));
+        assert_eq!(
+            territory.owned(&id('a')).count() as u32,
+            territory.count(&id('a'))
+        );
+
+        // Moving towards the middle takes cells from the other snake.
+        let closer = engine.territory_with(&Point::new(2, 3));
+        assert!(closer.count(&id('a')) > territory.count(&id('a')));
+        assert_eq!(closer.owner(&Point::new(3, 3)), Some(id('a')));
+        assert_eq!(
+            engine.area_control(&Point::new(2, 3)),
+            closer.count(&id('a'))
+        );
+    }
 
-            visited.insert(point);
+    /// A 5x3 board with us in the top left corner, and a shorter snake on the board's middle column, which is in
+    /// our way until it moves.
+    fn walled_in(game_type: GameType) -> Engine {
+        let state = TestState::new(5, 3)
+            .game_type(game_type)
+            .snake('a', 90, &[(0, 2), (0, 1), (0, 0), (1, 0)])
+            .snake('b', 90, &[(2, 2), (2, 1), (2, 0)])
+            .build();
 
-            area += 1;
+        Engine::new(EngineConfig::default(), state)
+    }
 
-            for neighbor in point.neighbors() {
-                if !self.is_unsafe(&point) && !visited.contains(&neighbor) {
-                    queue.push(neighbor);
-                }
+    #[test]
+    fn bodies_that_move_away_before_we_get_there_can_be_moved_through() {
+        // By the time we get around to them, `b`'s body and our own have moved out of the way, so the whole board
+        // is ours, including our tail.
+        let engine = walled_in(GameType::Standard);
+        let reach = engine.area_accessible(&Point::new(1, 2));
+
+        assert_eq!(
+            reach,
+            Reach {
+                area: 15,
+                tail: true
             }
-        }
+        );
+        assert_eq!(engine.reach(Move::Right), reach);
+        assert_eq!(engine.reach(Move::Left), Reach::default());
+    }
 
-        area
+    #[test]
+    fn bodies_that_never_move_away_are_walls() {
+        // In constrictor games, nothing ever moves away, so we are stuck between `b` and ourselves.
+        let engine = walled_in(GameType::Constrictor);
+
+        assert_eq!(
+            engine.area_accessible(&Point::new(1, 2)),
+            Reach {
+                area: 2,
+                tail: false
+            }
+        );
     }
 }
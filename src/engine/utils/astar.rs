@@ -1,7 +1,9 @@
 // This is really just some code to run the A* algorithm on the given board.
 
-use crate::{engine::Engine, objects::point::Point};
-use itertools::Itertools;
+use crate::{
+    engine::Engine,
+    objects::{point::Point, simulator::MAX_HEALTH},
+};
 use std::{
     cmp::Reverse,
     collections::{BinaryHeap, HashMap},
@@ -12,11 +14,12 @@ use std::{
 impl Engine {
     /// Runs the A* algorithm on the given map, starting at the given positions and
     /// ending at the given position. Returns a path starting from any of the starting
-    /// points to the end point, if one exists. Otherwise, returns None. If `ends` is
-    /// empty, it panics.
+    /// points to the end point, if one exists. Otherwise, returns None. Paths that would
+    /// run us out of health (from hazards or starving) before we get to food are never
+    /// taken. If `ends` is empty, there is nowhere to go, so it returns None.
     pub fn astar_find(&self, start: &Point, ends: &[Point]) -> Option<Vec<Point>> {
         if ends.is_empty() {
-            panic!("'ends' should not be empty!");
+            return None;
         }
 
         // The queue of positions to check. Initialized with the starting positions.
@@ -28,6 +31,10 @@ impl Engine {
         let mut f_score: HashMap<Point, u32> = HashMap::new();
         // The map from a point to the point it came from with the best path.
         let mut came_from: HashMap<Point, Point> = HashMap::new();
+        // The number of moves it takes to get to a point from the start, along the best path.
+        let mut steps: HashMap<Point, u32> = HashMap::new();
+        // How much health we would have left at a point, along the best path.
+        let mut health: HashMap<Point, i32> = HashMap::new();
 
         // Initalize the search queue with the starting positions.
         search_queue.push(Reverse(WeightedPoint {
@@ -37,10 +44,11 @@ impl Engine {
 
         // Initialize the scores of the starting positions to their distance from the end.
         g_score.insert(*start, 0);
+        steps.insert(*start, 0);
+        health.insert(*start, self.health());
         f_score.insert(
             *start,
-            start
-                .closest_distance(ends)
+            self.closest_distance(start, ends)
                 .expect("'ends' should not be empty!"),
         );
 
@@ -63,7 +71,21 @@ impl Engine {
             for neighbor in self.safe_neighbors(&point) {
                 // The tentative score is the current score of `point`, plus the point's own score, *plus*
                 // the distance from the neighbor to the end.
-                let tentative_g_score = g_score[&point] + self.g_score(&neighbor);
+                let neighbor_steps = steps[&point] + 1;
+
+                // Every move costs one health, plus whatever the hazards there deal. Food fills us back up.
+                // If we'd be out of health by the time we got to the neighbor, we can't go there.
+                let neighbor_health = if self.grid.is_food(&neighbor) {
+                    MAX_HEALTH
+                } else {
+                    health[&point] - 1 - self.hazard_damage(&neighbor, neighbor_steps)
+                };
+
+                if neighbor_health <= 0 {
+                    continue;
+                }
+
+                let tentative_g_score = g_score[&point] + self.g_score(&neighbor, neighbor_steps);
 
                 // If the neighbor has not been encountered yet, or the tentative score is better than the
                 // current score, update the score and the came_from map.
@@ -73,13 +95,15 @@ impl Engine {
                     f_score.insert(
                         neighbor,
                         tentative_g_score
-                            + neighbor
-                                .closest_distance(ends)
+                            + self
+                                .closest_distance(&neighbor, ends)
                                 .expect("'ends' should not be empty!"),
                     );
 
                     // Insert into the came_from map, so that if we find the end, we can trace back the path.
                     came_from.insert(neighbor, point);
+                    steps.insert(neighbor, neighbor_steps);
+                    health.insert(neighbor, neighbor_health);
 
                     // Insert into the search queue.
                     search_queue.push(Reverse(WeightedPoint::new(neighbor, f_score[&neighbor])));
@@ -93,29 +117,34 @@ impl Engine {
 
     /// Scoring heuristic for the A* algorithm: the score of a point is the cost of moving to that point
     /// (the h_score is the distance from the point to the end, which is calculated in the A* algorithm).
-    fn g_score(&self, point: &Point) -> u32 {
+    /// `steps` is how many moves it takes to get to the point, so that we know what the board will look
+    /// like by the time we get there.
+    fn g_score(&self, point: &Point, steps: u32) -> u32 {
+        let costs = self.config.costs;
+
+        // If our next move would take the last way out an ally has left, it would be trapped (or run into our head),
+        // so we only want to do that if every other path is a lot worse.
+        if steps == 1 && self.blocks_ally(point) {
+            costs.ally_exit
+        }
         // If the point is a food, we would like to eat it. Usually, that would mean that the cost is negative,
         // however, there was a bug with that. If two food items appeared next to each other, the A* algorithm
         // would get stuck in an infinite loop, because it would keep switching between the two food items (and
-        // giving them more and more negative scores). Thus, the score must be 0.
-        if self.food().contains(point) {
-            0
+        // giving them more and more negative scores). Thus, the score can't be negative.
+        else if self.grid.is_food(point) {
+            costs.food
         }
-        // If the point is a neighbor of a snake's head, we want to avoid it.
+        // If the point is a neighbor of an enemy snake's head, we want to avoid it.
         else if self
-            .other_snakes()
-            .any(|snake| snake.head.neighbors().contains(point))
+            .enemy_snakes()
+            .any(|snake| self.neighbors(&snake.head).contains(point))
         {
-            3
+            costs.near_head
         }
-        // If the point is a hazard, we *really* want to avoid it, because it will kill us faster.
-        // TODO: Differentiate between lethal and non-lethal hazards depending on game type.
-        else if self.is_hazard(point) {
-            16
-        }
-        // Otherwise, the point is safe, however every move decreases our health by 1.
+        // Otherwise, every move decreases our health by 1, and hazards (including ones that will be there by the
+        // time we get there) drain it by however much damage they deal. Stacked hazards cost more.
         else {
-            1
+            costs.normal + costs.hazard * self.hazard_damage(point, steps) as u32
         }
     }
 }
@@ -139,12 +168,87 @@ impl WeightedPoint {
 
 impl PartialOrd for WeightedPoint {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.weight.cmp(&other.weight))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for WeightedPoint {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.weight.cmp(&self.weight)
+        self.weight.cmp(&other.weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::engine::EngineConfig,
+        objects::{settings::GameType, testing::TestState},
+    };
+
+    #[test]
+    fn cheapest_points_come_off_the_queue_first() {
+        let mut queue = [5, 1, 9, 3, 7]
+            .into_iter()
+            .map(|weight| Reverse(WeightedPoint::new(Point::new(0, 0), weight)))
+            .collect::<BinaryHeap<_>>();
+
+        let order = std::iter::from_fn(|| queue.pop().map(|Reverse(point)| point.weight))
+            .collect::<Vec<_>>();
+
+        assert_eq!(order, vec![1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn finds_the_shortest_path() {
+        let state = TestState::new(11, 11)
+            .snake('a', 90, &[(1, 1), (1, 0), (0, 0)])
+            .build();
+        let engine = Engine::new(EngineConfig::default(), state);
+
+        let path = engine
+            .astar_find(&Point::new(1, 1), &[Point::new(8, 4)])
+            .expect("There should be a path.");
+
+        assert_eq!(path.len(), 11);
+        assert_eq!(path.first(), Some(&Point::new(1, 1)));
+        assert_eq!(path.last(), Some(&Point::new(8, 4)));
+    }
+
+    #[test]
+    fn finds_the_path_across_the_edge_in_wrapped_games() {
+        let state = TestState::new(11, 11)
+            .game_type(GameType::Wrapped)
+            .snake('a', 90, &[(1, 5), (2, 5), (3, 5)])
+            .build();
+        let engine = Engine::new(EngineConfig::default(), state);
+
+        let path = engine
+            .astar_find(&Point::new(1, 5), &[Point::new(9, 4)])
+            .expect("There should be a path.");
+
+        assert_eq!(path.len(), 5);
+        assert!(path.contains(&Point::new(0, 5)) || path.contains(&Point::new(0, 4)));
+        assert!(path.contains(&Point::new(10, 4)));
+    }
+
+    #[test]
+    fn paths_through_stacked_hazards_that_kill_us_are_rejected() {
+        // The only way to the food is through a wall of hazards, which we can get through with one stack, but not two.
+        let wall = [(2, 0), (2, 1), (2, 2)];
+        let build = |stacks: usize| {
+            let state = TestState::new(5, 3)
+                .snake('a', 20, &[(0, 1), (0, 0), (1, 0)])
+                .food(&[(4, 1)])
+                .hazards(&wall.repeat(stacks))
+                .build();
+            Engine::new(EngineConfig::default(), state)
+        };
+
+        let path = build(1).astar_find(&Point::new(0, 1), &[Point::new(4, 1)]);
+        assert_eq!(path.map(|path| path.len()), Some(5));
+
+        let path = build(2).astar_find(&Point::new(0, 1), &[Point::new(4, 1)]);
+        assert_eq!(path, None);
     }
 }
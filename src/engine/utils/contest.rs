@@ -0,0 +1,34 @@
+// Works out which food we would get to first. Only enemies contest food with us: food our allies get to first can
+// still be ours. See `Grid::food_contests` for how the contests are decided.
+
+use crate::{
+    engine::Engine,
+    objects::{contest::FoodContest, point::Point},
+};
+use std::collections::HashMap;
+
+/// Engine API for food contests.
+impl Engine {
+    /// Works out who gets to every piece of food on the board first. Only enemies are counted, so food our
+    /// allies get to first can still be ours.
+    pub fn food_contests(&self) -> Vec<FoodContest> {
+        let Some(slot) = self.grid.slot(&self.state.you.id) else {
+            return Vec::new();
+        };
+
+        self.grid
+            .food_contests(slot, self.state.board.food.iter().copied(), |id| {
+                self.enemy_snakes().any(|snake| snake.id == *id)
+            })
+    }
+
+    /// Returns how many moves it takes us to get from `start` to every cell that can be reached from it. See
+    /// `Grid::distances`.
+    pub fn path_distances(&self, start: &Point) -> HashMap<Point, u32> {
+        let Some(slot) = self.grid.slot(&self.state.you.id) else {
+            return HashMap::new();
+        };
+
+        self.grid.distances(slot, start, 0)
+    }
+}
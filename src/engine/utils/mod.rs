@@ -1,5 +1,7 @@
 pub mod area;
 pub mod astar;
+pub mod contest;
+pub mod rcsp;
 
 use super::Engine;
 use crate::objects::{moves::Move, point::Point};
@@ -13,11 +15,40 @@ impl Engine {
         self.state.board.other_snakes(&self.state.you.id)
     }
 
+    /// Get all the other snakes in the game that are not on our squad. Outside of squad games, this is every
+    /// other snake.
+    pub fn enemy_snakes(&self) -> impl Iterator<Item = &crate::objects::snake::Snake> {
+        self.other_snakes()
+            .filter(move |snake| !self.is_ally(snake))
+    }
+
     /// Checks if a point is on the board
     pub fn is_on_board(&self, point: &Point) -> bool {
         self.state.board.is_on_board(point)
     }
 
+    /// Gets all the neighbors of a point that are on the board, wrapping around the edges if the game is
+    /// wrapped.
+    pub fn neighbors(&self, point: &Point) -> Vec<Point> {
+        self.state.board.neighbors(point)
+    }
+
+    /// Gets the distance between two points, going around the edges if the game is wrapped.
+    pub fn distance(&self, a: &Point, b: &Point) -> u32 {
+        self.state.board.distance(a, b)
+    }
+
+    /// Gets the closest distance of a point to a list of points, going around the edges if the game is
+    /// wrapped.
+    pub fn closest_distance(&self, point: &Point, points: &[Point]) -> Option<u32> {
+        self.state.board.closest_distance(point, points)
+    }
+
+    /// Gets the move that gets from one point to a neighboring point.
+    pub fn move_between(&self, start: &Point, end: &Point) -> Option<Move> {
+        self.state.board.move_between(start, end)
+    }
+
     /// Returns an iterator of all safe moves.
     pub fn safe_moves(&self) -> impl Iterator<Item = Move> + '_ {
         let head = self.head();
@@ -25,7 +56,7 @@ impl Engine {
         Move::all()
             .iter()
             .copied()
-            .filter(move |&move_| !self.is_unsafe(&move_.to_point(head)))
+            .filter(move |&move_| !self.is_unsafe(&self.state.board.move_point(head, move_)))
             .collect::<Vec<_>>()
             .into_iter()
     }
@@ -37,15 +68,13 @@ impl Engine {
         info!("Choosing a random safe move.");
 
         let safe_move = self
-            .head()
-            .neighbors()
-            .iter()
-            .filter(|&n| !self.is_unsafe(n))
-            .choose(&mut rand::thread_rng())
-            .copied();
+            .neighbors(self.head())
+            .into_iter()
+            .filter(|n| !self.is_unsafe(n))
+            .choose(&mut rand::thread_rng());
 
         if let Some(safe_move) = safe_move {
-            Move::from_points(self.head(), &safe_move)
+            self.move_between(self.head(), &safe_move)
                 .expect("A* paths should generate valid moves.")
         } else {
             warn!("There are no safe moves available. Returning a random move.");
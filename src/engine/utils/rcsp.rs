@@ -0,0 +1,181 @@
+// A resource-constrained shortest path search to food. Health is the resource: every move costs one health, and
+// hazards cost however much damage they deal on top of that. The shortest path to a food isn't always one we survive,
+// so this finds the food we can get to while spending the least health, and never takes a path that would run us
+// out of health on the way.
+//
+// Because royale hazards spread over time, how much a cell costs depends on when we get there. So a label (a way of
+// getting to a cell) is only thrown away if another label got there both cheaper and sooner.
+
+use crate::{engine::Engine, objects::point::Point};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// The cheapest food we can reach alive, and how to get there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoodPath {
+    /// The food.
+    pub food: Point,
+
+    /// The path to the food, starting from our head and ending on the food.
+    pub path: Vec<Point>,
+
+    /// How much health we will have when we arrive at the food, right before eating it.
+    pub health: i32,
+}
+
+/// A way of getting to a cell.
+#[derive(Debug, Clone, Copy)]
+struct Label {
+    /// The cell.
+    point: Point,
+
+    /// The label this one was extended from, if any.
+    parent: Option<usize>,
+}
+
+/// Engine API for health-constrained pathfinding.
+impl Engine {
+    /// Finds the food out of `foods` we can get to while spending the least health, without ever running out
+    /// of health on the way. Food a hungrier ally is going for is left to them. Returns None if none of the
+    /// food can be reached alive, or right away if there isn't any food to go for.
+    pub fn cheapest_food(&self, foods: &[Point]) -> Option<FoodPath> {
+        if foods.is_empty() {
+            return None;
+        }
+
+        let health = self.health();
+
+        let mut labels = vec![Label {
+            point: *self.head(),
+            parent: None,
+        }];
+        // The (cost, steps, label) of every label that hasn't been extended yet, cheapest (and then soonest) first.
+        let mut queue = BinaryHeap::from([Reverse((0, 0, 0))]);
+        // The (cost, steps) of every label that has been kept for every cell, none of which dominate each other.
+        let mut kept: HashMap<Point, Vec<(i32, u32)>> = HashMap::new();
+
+        while let Some(Reverse((cost, steps, index))) = queue.pop() {
+            let label = labels[index];
+
+            // The first food we take off the queue is the cheapest one to get to.
+            if label.parent.is_some()
+                && foods.contains(&label.point)
+                && !self.is_ally_food(&label.point)
+            {
+                return Some(FoodPath {
+                    food: label.point,
+                    path: Self::trace(&labels, index),
+                    health: health - cost,
+                });
+            }
+
+            for neighbor in self.safe_neighbors(&label.point) {
+                let next_steps = steps + 1;
+                let next_cost = cost + 1 + self.hazard_damage(&neighbor, next_steps);
+
+                // We can only get somewhere if we still have health left when we get there. Food is the exception,
+                // because snakes get to eat before they are eliminated for starving.
+                if next_cost > health || next_cost == health && !self.grid.is_food(&neighbor) {
+                    continue;
+                }
+
+                let kept = kept.entry(neighbor).or_default();
+
+                if kept.iter().any(|&(kept_cost, kept_steps)| {
+                    kept_cost <= next_cost && kept_steps <= next_steps
+                }) {
+                    continue;
+                }
+
+                kept.retain(|&(kept_cost, kept_steps)| {
+                    kept_cost < next_cost || kept_steps < next_steps
+                });
+                kept.push((next_cost, next_steps));
+
+                labels.push(Label {
+                    point: neighbor,
+                    parent: Some(index),
+                });
+                queue.push(Reverse((next_cost, next_steps, labels.len() - 1)));
+            }
+        }
+
+        None
+    }
+}
+
+/// Private API for health-constrained pathfinding.
+impl Engine {
+    /// Traces the path to the label at `index` back to the start.
+    fn trace(labels: &[Label], mut index: usize) -> Vec<Point> {
+        let mut path = vec![labels[index].point];
+
+        while let Some(parent) = labels[index].parent {
+            path.push(labels[parent].point);
+            index = parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{configuration::engine::EngineConfig, objects::testing::TestState};
+
+    /// An engine for a 5x3 board with us in the middle of the left edge, with the given health, food and hazards.
+    fn engine(health: i32, food: &[(i32, i32)], hazards: &[(i32, i32)]) -> Engine {
+        let state = TestState::new(5, 3)
+            .snake('a', health, &[(0, 1), (0, 0), (1, 0)])
+            .food(food)
+            .hazards(hazards)
+            .build();
+
+        Engine::new(EngineConfig::default(), state)
+    }
+
+    #[test]
+    fn short_paths_through_deadly_hazards_are_passed_over_for_longer_ones() {
+        // Going straight right takes us through a hazard we wouldn't survive, so we have to go around it.
+        let engine = engine(10, &[(2, 1)], &[(1, 1)]);
+
+        let food = engine
+            .cheapest_food(&[Point::new(2, 1)])
+            .expect("The food should be reachable.");
+
+        assert_eq!(
+            food.path,
+            vec![
+                Point::new(0, 1),
+                Point::new(0, 2),
+                Point::new(1, 2),
+                Point::new(2, 2),
+                Point::new(2, 1),
+            ]
+        );
+        assert_eq!(food.health, 6);
+    }
+
+    #[test]
+    fn food_we_would_starve_on_the_way_to_is_unreachable() {
+        // The food is 4 moves away, so we need at least 4 health to get there, since we eat before we starve.
+        let food = [Point::new(4, 1)];
+
+        assert_eq!(engine(3, &[(4, 1)], &[]).cheapest_food(&food), None);
+        assert_eq!(
+            engine(4, &[(4, 1)], &[])
+                .cheapest_food(&food)
+                .map(|food| food.health),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn there_is_no_cheapest_food_without_food() {
+        assert_eq!(engine(90, &[], &[]).cheapest_food(&[]), None);
+    }
+}
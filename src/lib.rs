@@ -0,0 +1,4 @@
+pub mod configuration;
+pub mod engine;
+pub mod objects;
+pub mod server;
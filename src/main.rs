@@ -1,9 +1,4 @@
-pub mod configuration;
-pub mod engine;
-pub mod objects;
-pub mod server;
-
-use crate::{objects::GameState, server::Server};
+use hematite::{objects::GameState, server::Server};
 use log::info;
 use rocket::fairing::AdHoc;
 use rocket::http::Status;
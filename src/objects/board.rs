@@ -1,9 +1,11 @@
 use super::{
+    moves::Move,
     point::Point,
+    settings::GameType,
     snake::{Snake, SnakeID},
 };
 use rocket::serde::{Deserialize, Serialize};
-use serde::Deserializer;
+use serde::{Deserializer, Serializer};
 use std::collections::{HashMap, HashSet};
 
 /// The board where the game is played.
@@ -22,24 +24,97 @@ pub struct Board {
     /// The set of all food on the board.
     pub food: HashSet<Point>,
 
-    /// The set of all hazards on the board.
-    pub hazards: HashSet<Point>,
+    /// All the hazards on the board, with how many hazards are stacked on each cell. The API sends stacked
+    /// hazards as the same point appearing multiple times, and every one of them deals damage.
+    #[serde(
+        deserialize_with = "Board::parse_hazards",
+        serialize_with = "Board::serialize_hazards"
+    )]
+    pub hazards: HashMap<Point, u32>,
+
+    /// The type of game being played on the board. This isn't sent by the API as part of the board, but
+    /// is set from the game's ruleset when the game state is parsed.
+    #[serde(skip)]
+    pub game_type: GameType,
+
+    /// Whether squadmates can move through each other's bodies. Like `game_type`, this is set from the game's
+    /// ruleset when the game state is parsed.
+    #[serde(skip)]
+    pub allow_body_collisions: bool,
 }
 
 /// Public API for the board.
 impl Board {
-    /// Gets all the orthogonal neighbors of a coordinate point on the board
+    /// Gets all the orthogonal neighbors of a coordinate point on the board. On wrapped boards, neighbors
+    /// past the edges wrap around to the opposite side.
     pub fn neighbors(&self, point: &Point) -> Vec<Point> {
         let mut neighbors = point.neighbors();
-        neighbors.retain(|point| self.is_on_board(point));
+
+        if self.is_wrapped() {
+            neighbors
+                .iter_mut()
+                .for_each(|point| *point = point.wrap(self.width, self.height));
+        } else {
+            neighbors.retain(|point| self.is_on_board(point));
+        }
+
         neighbors
     }
 
+    /// Returns true if the edges of the board wrap around to the opposite side.
+    pub fn is_wrapped(&self) -> bool {
+        self.game_type == GameType::Wrapped
+    }
+
     /// Checks if a coordinate is on the board.
     pub fn is_on_board(&self, point: &Point) -> bool {
         point.x >= 0 && point.x < self.width as i32 && point.y >= 0 && point.y < self.height as i32
     }
 
+    /// Gets the distance between two coordinates on the board, going around the edges if the board is
+    /// wrapped.
+    pub fn distance(&self, a: &Point, b: &Point) -> u32 {
+        if self.is_wrapped() {
+            a.wrapped_distance(b, self.width, self.height)
+        } else {
+            a.distance(b)
+        }
+    }
+
+    /// Gets the closest distance of a coordinate to a list of coordinates on the board.
+    pub fn closest_distance(&self, point: &Point, points: &[Point]) -> Option<u32> {
+        points.iter().map(|other| self.distance(point, other)).min()
+    }
+
+    /// Turns a move into an ending coordinate given a starting coordinate. On wrapped boards, moving off
+    /// an edge ends up on the opposite edge.
+    pub fn move_point(&self, start: &Point, move_: Move) -> Point {
+        let end = move_.to_point(start);
+
+        if self.is_wrapped() {
+            end.wrap(self.width, self.height)
+        } else {
+            end
+        }
+    }
+
+    /// Turns two neighboring coordinates into the move that gets from `start` to `end`.
+    pub fn move_between(&self, start: &Point, end: &Point) -> Option<Move> {
+        Move::all()
+            .into_iter()
+            .find(|move_| self.move_point(start, *move_) == *end)
+    }
+
+    /// Gets the move a snake makes if it doesn't give one. This is the direction it was last moving in,
+    /// or `Move::Up` if it hasn't moved yet (i.e. its body is still stacked on its head).
+    pub fn default_move(&self, snake: &Snake) -> Move {
+        snake
+            .body
+            .get(1)
+            .and_then(|neck| self.move_between(neck, &snake.head))
+            .unwrap_or(Move::Up)
+    }
+
     /// Gets all the other snakes on the board. Returns an iterator.
     pub fn other_snakes<'a>(&'a self, you: &'a SnakeID) -> impl Iterator<Item = &'a Snake> + 'a {
         self.snakes
@@ -58,4 +133,30 @@ impl Board {
         let snakes: Vec<Snake> = Vec::deserialize(deserializer)?;
         Ok(snakes.into_iter().map(|snake| (snake.id, snake)).collect())
     }
+
+    /// Parse the hazards on the board, counting how many are stacked on every cell.
+    fn parse_hazards<'de, D>(deserializer: D) -> Result<HashMap<Point, u32>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hazards: Vec<Point> = Vec::deserialize(deserializer)?;
+        Ok(hazards
+            .into_iter()
+            .fold(HashMap::new(), |mut stacks, hazard| {
+                *stacks.entry(hazard).or_default() += 1;
+                stacks
+            }))
+    }
+
+    /// Serialize the hazards on the board the way the API sends them, repeating stacked hazards.
+    fn serialize_hazards<S>(hazards: &HashMap<Point, u32>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(
+            hazards
+                .iter()
+                .flat_map(|(hazard, stack)| std::iter::repeat_n(hazard, *stack as usize)),
+        )
+    }
 }
@@ -0,0 +1,143 @@
+// Works out who gets to which food first. The nearest food isn't worth much if a rival can get there before us, or
+// gets there at the same time and is longer than us. Distances are real path lengths, found with a breadth-first
+// search from every snake's head that goes around bodies (and through them, once they have moved out of the way).
+// Both the engine (when it's hungry) and the evaluator (when it judges a state) use this, so they agree on which
+// food is ours.
+
+use super::{grid::Grid, point::Point, snake::SnakeID};
+use std::cmp::Ordering;
+
+/// Who gets to a piece of food first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Contest {
+    /// We get there first, or at the same time as a shorter snake.
+    Win,
+
+    /// Someone else gets there first, or at the same time as us and is longer. Also food we can't get to.
+    Lose,
+
+    /// We get there at the same time as a snake that is as long as us.
+    Tie,
+}
+
+/// The contest over a single piece of food.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoodContest {
+    /// The food.
+    pub food: Point,
+
+    /// How many moves it takes us to get to the food, if we can get there at all.
+    pub distance: Option<u32>,
+
+    /// Who gets there first.
+    pub contest: Contest,
+}
+
+/// Food contests on the grid.
+impl Grid {
+    /// Works out who gets to every piece of `food` first, between the snake in the given slot and every snake
+    /// `is_rival` returns true for. Snakes that aren't rivals (like allies) don't contest any food.
+    pub fn food_contests(
+        &self,
+        slot: usize,
+        food: impl IntoIterator<Item = Point>,
+        is_rival: impl Fn(&SnakeID) -> bool,
+    ) -> Vec<FoodContest> {
+        let snakes = self.snakes();
+        let length = snakes[slot].body.len();
+        let ours = self.distances(slot, &snakes[slot].head(), 0);
+        let theirs = snakes
+            .iter()
+            .enumerate()
+            .filter(|(other, snake)| *other != slot && is_rival(&snake.id))
+            .map(|(other, snake)| (snake.body.len(), self.distances(other, &snake.head(), 0)))
+            .collect::<Vec<_>>();
+
+        food.into_iter()
+            .map(|food| {
+                let distance = ours.get(&food).copied();
+
+                let contest = match distance {
+                    None => Contest::Lose,
+                    Some(distance) => theirs
+                        .iter()
+                        .filter_map(|(other, distances)| {
+                            distances
+                                .get(&food)
+                                .map(|rival| match rival.cmp(&distance) {
+                                    Ordering::Less => Contest::Lose,
+                                    Ordering::Greater => Contest::Win,
+                                    Ordering::Equal if *other < length => Contest::Win,
+                                    Ordering::Equal if *other > length => Contest::Lose,
+                                    Ordering::Equal => Contest::Tie,
+                                })
+                        })
+                        .fold(Contest::Win, |worst, contest| match (worst, contest) {
+                            (Contest::Lose, _) | (_, Contest::Lose) => Contest::Lose,
+                            (Contest::Tie, _) | (_, Contest::Tie) => Contest::Tie,
+                            _ => Contest::Win,
+                        }),
+                };
+
+                FoodContest {
+                    food,
+                    distance,
+                    contest,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::{
+        settings::GameType,
+        testing::{id, TestState},
+    };
+
+    /// Snake `a` is on a squad with `b`, against `c`, which is as long as `a`. The food at (3, 5) is as far from
+    /// `a` as it is from `c`, the food at (0, 0) is closer to `b`, and the food at (5, 0) is closer to `a`.
+    fn contests(is_rival: impl Fn(&SnakeID) -> bool) -> Vec<FoodContest> {
+        let state = TestState::new(7, 7)
+            .game_type(GameType::Squad)
+            .squad_snake('a', "red", 90, &[(3, 2), (3, 1), (3, 0)])
+            .squad_snake('b', "red", 90, &[(0, 1), (0, 2), (0, 3)])
+            .squad_snake('c', "blue", 90, &[(3, 6), (4, 6), (5, 6)])
+            .food(&[(3, 4), (0, 0), (5, 2)])
+            .build();
+        let grid = Grid::new(&state.board);
+        let slot = grid
+            .slot(&id('a'))
+            .expect("Snake a should be on the board.");
+
+        grid.food_contests(slot, state.board.food.iter().copied(), is_rival)
+    }
+
+    /// Finds the contest for the food at the given point.
+    fn contest_at(contests: &[FoodContest], food: (i32, i32)) -> Contest {
+        contests
+            .iter()
+            .find(|contest| contest.food == food.into())
+            .expect("Every food should be contested.")
+            .contest
+    }
+
+    #[test]
+    fn equally_long_rivals_tie() {
+        let contests = contests(|other| *other != id('b'));
+
+        assert_eq!(contest_at(&contests, (3, 4)), Contest::Tie);
+        assert_eq!(contest_at(&contests, (5, 2)), Contest::Win);
+    }
+
+    #[test]
+    fn only_rivals_contest_food() {
+        let allies_are_rivals = contests(|_| true);
+        let allies_are_not = contests(|other| *other != id('b'));
+
+        assert_eq!(contest_at(&allies_are_rivals, (0, 0)), Contest::Lose);
+        assert_eq!(contest_at(&allies_are_not, (0, 0)), Contest::Win);
+    }
+}
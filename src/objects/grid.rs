@@ -0,0 +1,447 @@
+// A dense representation of the board for fast lookups. `Board` is great for (de)serializing, but it stores food and
+// hazards in `HashSet`s and snake bodies in `Vec`s, so checking what is on a cell means hashing or scanning through
+// every snake. The grid stores what is on every cell in a flat array instead, so every lookup is a single index. It is
+// a snapshot of a single board: the engine builds one per request for its rules and pathfinding, and searches build
+// one for every state they need to look up cells in, and hand it to everything that looks at that state (including
+// the evaluator). `benches/grid.rs` compares lookups on the grid against lookups on the `Board`.
+
+use super::{
+    moves::Move,
+    point::Point,
+    settings::GameType,
+    snake::{Snake, SnakeID},
+    Board,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A dense grid of all the cells on the board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    /// The width of the board.
+    pub width: u32,
+
+    /// The height of the board.
+    pub height: u32,
+
+    /// The type of game being played on the board.
+    pub game_type: GameType,
+
+    /// Every cell on the board, stored row by row.
+    cells: Vec<Cell>,
+
+    /// Every snake on the board. A snake's position in this list is its "slot".
+    snakes: Vec<GridSnake>,
+}
+
+/// What is on a single cell of the board.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    /// Whether there is food on the cell.
+    pub food: bool,
+
+    /// How many hazards are stacked on the cell.
+    pub hazards: u32,
+
+    /// The snake segment on the cell, if any.
+    pub segment: Option<Segment>,
+}
+
+/// A segment of a snake's body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    /// The slot of the snake the segment belongs to.
+    pub snake: usize,
+
+    /// How far the segment is from the snake's head, where the head is 0. If multiple segments are
+    /// stacked on the same cell, this is the one closest to the head, as that is the last one to leave.
+    pub index: usize,
+}
+
+/// A snake on the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridSnake {
+    /// The snake's ID.
+    pub id: SnakeID,
+
+    /// How much health the snake has.
+    pub health: i32,
+
+    /// The snake's body, ordered from head to tail.
+    pub body: Vec<Point>,
+
+    /// The squad the snake is on, if this is a squad game where squadmates can move through each other's
+    /// bodies. Otherwise, this is `None`.
+    pub squad: Option<String>,
+}
+
+/// Public API for the grid.
+impl Grid {
+    /// Creates a new grid from the given board.
+    pub fn new(board: &Board) -> Self {
+        let mut grid = Self {
+            width: board.width,
+            height: board.height,
+            game_type: board.game_type,
+            cells: vec![Cell::default(); (board.width * board.height) as usize],
+            snakes: Vec::with_capacity(board.snakes.len()),
+        };
+
+        for food in &board.food {
+            if let Some(cell) = grid.cell_mut(food) {
+                cell.food = true;
+            }
+        }
+
+        for (hazard, stack) in &board.hazards {
+            if let Some(cell) = grid.cell_mut(hazard) {
+                cell.hazards = *stack;
+            }
+        }
+
+        let allow_body_collisions =
+            board.game_type == GameType::Squad && board.allow_body_collisions;
+
+        for snake in board.snakes.values() {
+            grid.add_snake(snake, allow_body_collisions);
+        }
+
+        grid
+    }
+
+    /// Checks if a coordinate is on the board.
+    pub fn is_on_board(&self, point: &Point) -> bool {
+        self.index(point).is_some()
+    }
+
+    /// Turns a move into an ending coordinate given a starting coordinate. On wrapped boards, moving off
+    /// an edge ends up on the opposite edge.
+    pub fn move_point(&self, start: &Point, move_: Move) -> Point {
+        let end = move_.to_point(start);
+
+        if self.game_type == GameType::Wrapped {
+            end.wrap(self.width, self.height)
+        } else {
+            end
+        }
+    }
+
+    /// Gets all the orthogonal neighbors of a coordinate that are on the board.
+    pub fn neighbors(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        let point = *point;
+
+        Move::all()
+            .into_iter()
+            .map(move |move_| self.move_point(&point, move_))
+            .filter(|neighbor| self.is_on_board(neighbor))
+    }
+
+    /// Gets the cell at the given point, or `None` if the point is not on the board.
+    pub fn cell(&self, point: &Point) -> Option<&Cell> {
+        self.index(point).map(|index| &self.cells[index])
+    }
+
+    /// Returns true if there is food at the given point.
+    pub fn is_food(&self, point: &Point) -> bool {
+        self.cell(point).is_some_and(|cell| cell.food)
+    }
+
+    /// Returns true if there is a hazard at the given point.
+    pub fn is_hazard(&self, point: &Point) -> bool {
+        self.hazards(point) > 0
+    }
+
+    /// Gets how many hazards are stacked at the given point.
+    pub fn hazards(&self, point: &Point) -> u32 {
+        self.cell(point).map_or(0, |cell| cell.hazards)
+    }
+
+    /// Gets the snake segment at the given point, if any.
+    pub fn segment(&self, point: &Point) -> Option<Segment> {
+        self.cell(point).and_then(|cell| cell.segment)
+    }
+
+    /// Gets all the snakes on the grid, in slot order.
+    pub fn snakes(&self) -> &[GridSnake] {
+        &self.snakes
+    }
+
+    /// Gets the slot of the snake with the given ID.
+    pub fn slot(&self, id: &SnakeID) -> Option<usize> {
+        self.snakes.iter().position(|snake| snake.id == *id)
+    }
+
+    /// Returns how many turns it will take for the snake segment at the given point to move away, or 0 if
+    /// there is no segment there. A snake's tail moves away after 1 turn, the segment before it after 2
+    /// turns, and so on. This doesn't account for snakes eating while they move. In constrictor games,
+    /// snakes grow every turn, so their segments never move away, and this returns `usize::MAX`.
+    pub fn vacates_in(&self, point: &Point) -> usize {
+        match self.segment(point) {
+            None => 0,
+            Some(_) if self.game_type == GameType::Constrictor => usize::MAX,
+            Some(segment) => self.snakes[segment.snake].body.len() - segment.index,
+        }
+    }
+
+    /// Returns true if the snake in slot `mover` can move through the body of the snake in slot `other`. This
+    /// is only the case for squadmates, in squad games that allow body collisions.
+    pub fn can_pass_through(&self, mover: usize, other: usize) -> bool {
+        mover != other
+            && self.snakes[mover].squad.is_some()
+            && self.snakes[mover].squad == self.snakes[other].squad
+    }
+
+    /// Like `vacates_in`, but from the point of view of the snake in the given slot: segments it can move
+    /// through are already out of its way.
+    pub fn vacates_for(&self, slot: usize, point: &Point) -> usize {
+        match self.segment(point) {
+            Some(segment) if self.can_pass_through(slot, segment.snake) => 0,
+            _ => self.vacates_in(point),
+        }
+    }
+
+    /// Returns all the moves the snake in the given slot can make without immediately running into a wall
+    /// or a body. Tails are counted as free, unless the snake just ate (and so its tail won't move), or it's
+    /// a constrictor game (where tails never move). Squadmates' bodies are free too, if the game lets them
+    /// move through each other. This does not consider head-to-head collisions, as those depend on the
+    /// other snakes' moves.
+    pub fn safe_moves(&self, slot: usize) -> Vec<Move> {
+        let head = self.snakes[slot].head();
+
+        Move::all()
+            .into_iter()
+            .filter(|move_| {
+                let point = self.move_point(&head, *move_);
+                self.is_on_board(&point) && self.vacates_for(slot, &point) <= 1
+            })
+            .collect()
+    }
+
+    /// Returns how many moves it takes the snake in the given slot to get from `start` to every cell that
+    /// can be reached from it, where it takes `depth` moves to get to `start` itself. A cell with a body on
+    /// it can be moved through once that part of the body has moved out of the way, or right away if the
+    /// snake can move through it (see `vacates_for`).
+    pub fn distances(&self, slot: usize, start: &Point, depth: u32) -> HashMap<Point, u32> {
+        let mut distances = HashMap::from([(*start, depth)]);
+        let mut queue = VecDeque::from([*start]);
+
+        while let Some(point) = queue.pop_front() {
+            let distance = distances[&point] + 1;
+
+            for neighbor in self.neighbors(&point) {
+                if distances.contains_key(&neighbor)
+                    || self.vacates_for(slot, &neighbor) > distance as usize
+                {
+                    continue;
+                }
+
+                distances.insert(neighbor, distance);
+                queue.push_back(neighbor);
+            }
+        }
+
+        distances
+    }
+
+    /// Works out which snake gets to which cells first, with a breadth-first search from every snake's head
+    /// at the same time. `heads` has the head to search from for every slot, and every snake can move through
+    /// what it could in `distances`. A snake gets a cell if it gets there before every other snake, or at the
+    /// same time as only shorter snakes. Returns the slot that gets every cell someone gets, and how many moves
+    /// it takes them.
+    pub fn voronoi(&self, heads: &[Point]) -> HashMap<Point, (usize, u32)> {
+        let mut owners = HashMap::new();
+        // Every cell that has been reached by someone, whether or not anyone gets it.
+        let mut reached = heads.iter().copied().collect::<HashSet<_>>();
+        let mut frontier = heads.iter().copied().enumerate().collect::<Vec<_>>();
+        let mut distance = 0;
+
+        while !frontier.is_empty() {
+            distance += 1;
+
+            // Every cell reached this step, and the snakes that got there.
+            let mut claims: HashMap<Point, Vec<usize>> = HashMap::new();
+
+            for (slot, point) in &frontier {
+                for neighbor in self.neighbors(point) {
+                    if reached.contains(&neighbor) || self.vacates_for(*slot, &neighbor) > distance
+                    {
+                        continue;
+                    }
+
+                    claims.entry(neighbor).or_default().push(*slot);
+                }
+            }
+
+            frontier.clear();
+
+            for (point, slots) in claims {
+                reached.insert(point);
+
+                let length = |slot: &usize| self.snakes[*slot].body.len();
+                let longest = slots.iter().map(length).max().unwrap_or(0);
+                let mut winners = slots
+                    .iter()
+                    .filter(|slot| length(slot) == longest)
+                    .collect::<HashSet<_>>()
+                    .into_iter();
+
+                if let (Some(&slot), None) = (winners.next(), winners.next()) {
+                    owners.insert(point, (slot, distance as u32));
+                    frontier.push((slot, point));
+                }
+            }
+        }
+
+        owners
+    }
+}
+
+impl GridSnake {
+    /// Gets the snake's head.
+    pub fn head(&self) -> Point {
+        *self.body.first().expect("All snakes should have a head.")
+    }
+
+    /// Gets the snake's tail.
+    pub fn tail(&self) -> Point {
+        *self.body.last().expect("All snakes should have a tail.")
+    }
+}
+
+/// Private API for the grid.
+impl Grid {
+    /// Gets the index of the given point in `cells`, or `None` if the point is not on the board.
+    fn index(&self, point: &Point) -> Option<usize> {
+        if point.x < 0
+            || point.y < 0
+            || point.x >= self.width as i32
+            || point.y >= self.height as i32
+        {
+            return None;
+        }
+
+        Some((point.y as u32 * self.width + point.x as u32) as usize)
+    }
+
+    /// Gets a mutable reference to the cell at the given point, or `None` if the point is not on the board.
+    fn cell_mut(&mut self, point: &Point) -> Option<&mut Cell> {
+        self.index(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Adds a snake to the grid, in the next free slot. If `allow_body_collisions` is set, its squad is kept
+    /// so that its squadmates can move through it.
+    fn add_snake(&mut self, snake: &Snake, allow_body_collisions: bool) {
+        let slot = self.snakes.len();
+
+        // Segments are added from the tail to the head, so stacked segments end up with the index of the
+        // segment closest to the head.
+        for (index, point) in snake.body.iter().enumerate().rev() {
+            if let Some(cell) = self.cell_mut(point) {
+                cell.segment = Some(Segment { snake: slot, index });
+            }
+        }
+
+        self.snakes.push(GridSnake {
+            id: snake.id,
+            health: snake.health,
+            body: snake.body.clone(),
+            squad: snake
+                .squad
+                .clone()
+                .filter(|squad| allow_body_collisions && !squad.is_empty()),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::{
+        settings::GameType,
+        testing::{id, TestState},
+    };
+
+    /// Snake `a` has its own tail to its right and snake `b`'s tail above it, with a wall to its left.
+    fn boxed_in(game_type: GameType) -> Grid {
+        let state = TestState::new(7, 7)
+            .game_type(game_type)
+            .snake('a', 90, &[(0, 1), (0, 0), (1, 0), (1, 1)])
+            .snake('b', 90, &[(3, 3), (2, 3), (1, 3), (0, 3), (0, 2)])
+            .build();
+
+        Grid::new(&state.board)
+    }
+
+    #[test]
+    fn tails_move_away_in_standard_games() {
+        let grid = boxed_in(GameType::Standard);
+        let slot = grid
+            .slot(&id('a'))
+            .expect("Snake a should be on the board.");
+
+        assert_eq!(grid.vacates_in(&Point::new(1, 1)), 1);
+        assert_eq!(grid.vacates_in(&Point::new(0, 2)), 1);
+        assert_eq!(grid.vacates_in(&Point::new(1, 3)), 3);
+        assert_eq!(grid.safe_moves(slot), vec![Move::Up, Move::Right]);
+    }
+
+    #[test]
+    fn tails_never_move_away_in_constrictor_games() {
+        let grid = boxed_in(GameType::Constrictor);
+        let slot = grid
+            .slot(&id('a'))
+            .expect("Snake a should be on the board.");
+
+        assert_eq!(grid.vacates_in(&Point::new(1, 1)), usize::MAX);
+        assert_eq!(grid.vacates_in(&Point::new(0, 2)), usize::MAX);
+        assert_eq!(grid.vacates_in(&Point::new(3, 3)), usize::MAX);
+        assert_eq!(grid.vacates_in(&Point::new(4, 4)), 0);
+        assert!(grid.safe_moves(slot).is_empty());
+    }
+
+    /// Snake `a` has its squadmate `b` to its right, in a squad game that may let squadmates move through each
+    /// other.
+    fn squad(collisions: bool) -> Grid {
+        let state = TestState::new(7, 7)
+            .game_type(GameType::Squad)
+            .squad_rules(collisions, false, false, false)
+            .squad_snake('a', "red", 90, &[(1, 1), (1, 0), (0, 0)])
+            .squad_snake('b', "red", 90, &[(2, 3), (2, 2), (2, 1), (2, 0)])
+            .build();
+
+        Grid::new(&state.board)
+    }
+
+    #[test]
+    fn squadmates_move_through_each_other_when_the_rules_allow_it() {
+        for collisions in [true, false] {
+            let grid = squad(collisions);
+            let a = grid
+                .slot(&id('a'))
+                .expect("Snake a should be on the board.");
+            let b = grid
+                .slot(&id('b'))
+                .expect("Snake b should be on the board.");
+            let heads = grid
+                .snakes()
+                .iter()
+                .map(|snake| snake.head())
+                .collect::<Vec<_>>();
+
+            assert_eq!(grid.can_pass_through(a, b), collisions);
+            assert_eq!(grid.safe_moves(a).contains(&Move::Right), collisions);
+            assert_eq!(
+                grid.distances(a, &Point::new(1, 1), 0)
+                    .get(&Point::new(2, 1))
+                    == Some(&1),
+                collisions
+            );
+            // Without going through `b`, `a` has to go all the way around its own tail to get past it.
+            assert_eq!(
+                grid.voronoi(&heads)
+                    .get(&Point::new(3, 1))
+                    .map(|(slot, _)| *slot),
+                Some(if collisions { a } else { b })
+            );
+        }
+    }
+}
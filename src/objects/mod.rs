@@ -1,8 +1,13 @@
 pub mod board;
+pub mod contest;
+pub mod grid;
 pub mod moves;
 pub mod point;
 pub mod settings;
+pub mod simulator;
 pub mod snake;
+#[cfg(test)]
+pub mod testing;
 
 use self::{board::Board, settings::Ruleset, snake::Snake};
 use serde::{Deserialize, Serialize};
@@ -12,18 +17,47 @@ use uuid::Uuid;
 // See https://docs.battlesnake.com/api
 
 /// The object holding the game and it's settings.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Game {
     pub id: Uuid,
     pub ruleset: Ruleset,
     pub timeout: u32,
+    /// The name of the map the game is played on, if the API sent one.
+    #[serde(default)]
+    pub map: Option<String>,
 }
 
 /// The state of the game.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(from = "GameStateData")]
 pub struct GameState {
     pub game: Game,
     pub turn: u32,
     pub board: Board,
     pub you: Snake,
 }
+
+/// The state of the game exactly as the API sends it. The board doesn't know which ruleset it's being
+/// played with yet, so this is turned into a `GameState` that tells it.
+#[derive(Deserialize)]
+struct GameStateData {
+    game: Game,
+    turn: u32,
+    board: Board,
+    you: Snake,
+}
+
+impl From<GameStateData> for GameState {
+    fn from(data: GameStateData) -> Self {
+        let mut board = data.board;
+        board.game_type = data.game.ruleset.name;
+        board.allow_body_collisions = data.game.ruleset.settings.squad.allow_body_collisions;
+
+        Self {
+            game: data.game,
+            turn: data.turn,
+            board,
+            you: data.you,
+        }
+    }
+}
@@ -25,6 +25,16 @@ impl Move {
         [Self::Up, Self::Down, Self::Left, Self::Right]
     }
 
+    /// Returns the position of the move in `Move::all()`.
+    pub fn index(&self) -> usize {
+        match self {
+            Move::Up => 0,
+            Move::Down => 1,
+            Move::Left => 2,
+            Move::Right => 3,
+        }
+    }
+
     /// Returns a random move.
     pub fn random<R: rand::Rng>(rng: &mut R) -> Self {
         *Self::all()
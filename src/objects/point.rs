@@ -28,6 +28,25 @@ impl Point {
         (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
     }
 
+    /// Wraps the coordinate around the edges of a `width` by `height` board, so that it ends up on the
+    /// board. Used for wrapped games, where moving off one edge puts you on the opposite edge.
+    pub fn wrap(&self, width: u32, height: u32) -> Point {
+        (
+            self.x.rem_euclid(width as i32),
+            self.y.rem_euclid(height as i32),
+        )
+            .into()
+    }
+
+    /// Gets the manhattan distance between two coordinates on a `width` by `height` board that wraps
+    /// around at the edges, i.e. the distance is the shortest way around the board in either direction.
+    pub fn wrapped_distance(&self, other: &Point, width: u32, height: u32) -> u32 {
+        let x = (self.x - other.x).unsigned_abs() % width;
+        let y = (self.y - other.y).unsigned_abs() % height;
+
+        x.min(width - x) + y.min(height - y)
+    }
+
     /// Gets the closest distance of a point to a list of points.
     pub fn closest_distance(&self, points: &[Point]) -> Option<u32> {
         points.iter().map(|point| self.distance(point)).min()
@@ -45,3 +64,21 @@ impl From<Point> for (i32, i32) {
         (val.x, val.y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_distance_goes_around_both_axes() {
+        let distance =
+            |a: (i32, i32), b: (i32, i32)| Point::from(a).wrapped_distance(&b.into(), 11, 7);
+
+        assert_eq!(distance((0, 3), (10, 3)), 1);
+        assert_eq!(distance((5, 0), (5, 6)), 1);
+        assert_eq!(distance((0, 0), (10, 6)), 2);
+        assert_eq!(distance((1, 1), (9, 5)), 3 + 3);
+        assert_eq!(distance((2, 2), (5, 4)), 3 + 2);
+        assert_eq!(distance((10, 6), (0, 0)), distance((0, 0), (10, 6)));
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// The rules associated with the current game.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Ruleset {
     /// The name of the game type.
     pub name: GameType,
@@ -11,7 +11,7 @@ pub struct Ruleset {
 }
 
 /// The settings for the current game.
-#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RulesetSettings {
     /// The percent chance food has to spawn every round
@@ -42,7 +42,7 @@ impl RulesetSettings {
 }
 
 /// The settings for royale games.
-#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RulesetRoyaleSettings {
     /// The number of turns to wait before shrinking the map.
@@ -50,7 +50,7 @@ pub struct RulesetRoyaleSettings {
 }
 
 /// The settings for squad games.
-#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RulesetSquadSettings {
     /// If we allow snakes on the same squad to collide.
@@ -71,8 +71,10 @@ pub struct RulesetSquadSettings {
 /// - royale
 /// - constrictor
 /// - wrapped
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// - squad
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GameType {
+    #[default]
     #[serde(rename = "standard")]
     Standard,
 
@@ -84,4 +86,29 @@ pub enum GameType {
 
     #[serde(rename = "wrapped")]
     Wrapped,
+
+    #[serde(rename = "squad")]
+    Squad,
+}
+
+impl GameType {
+    /// Every game type.
+    pub const ALL: [GameType; 5] = [
+        GameType::Standard,
+        GameType::Royale,
+        GameType::Constrictor,
+        GameType::Wrapped,
+        GameType::Squad,
+    ];
+
+    /// Gets the name of the game type, as the API sends it.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameType::Standard => "standard",
+            GameType::Royale => "royale",
+            GameType::Constrictor => "constrictor",
+            GameType::Wrapped => "wrapped",
+            GameType::Squad => "squad",
+        }
+    }
 }
@@ -0,0 +1,788 @@
+// This lets us play the game forward ourselves. Given a move for every snake, we can advance a `GameState` by one
+// turn, exactly like the official standard ruleset does. See https://docs.battlesnake.com/guides/game/rules. In
+// constrictor games, every snake also grows and gets its health back at the end of every turn. In squad games,
+// squadmates can be allowed to pass through each other, and share their health, length and eliminations.
+//
+// Every turn that is applied records exactly what it changed, so that it can be undone again afterwards. This lets
+// searches walk up and down the game tree on a single state, instead of cloning the whole state for every node.
+
+use super::{
+    grid::Grid,
+    moves::Move,
+    point::Point,
+    settings::{GameType, RulesetSquadSettings},
+    snake::{Snake, SnakeID},
+    Board, GameState,
+};
+use std::collections::HashMap;
+
+/// The maximum health a snake can have. Eating food resets a snake's health to this.
+pub const MAX_HEALTH: i32 = 100;
+
+/// Everything that changed during a single turn, so that the turn can be undone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnDelta {
+    /// Every snake that moved during the turn.
+    moved: Vec<MovedSnake>,
+
+    /// The food that was eaten during the turn.
+    eaten: Vec<Point>,
+
+    /// The snakes that were eliminated during the turn, as they were when they were removed.
+    eliminated: Vec<Snake>,
+
+    /// The Zobrist keys of everything that changed during the turn, XOR'd together. The simulator doesn't know
+    /// about any keys, so this stays 0 unless a search fills it in (see `Zobrist::apply`).
+    pub hash: u64,
+}
+
+/// How a single snake changed during a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovedSnake {
+    /// The snake's ID.
+    pub id: SnakeID,
+
+    /// The tail the snake left behind when it moved.
+    pub tail: Point,
+
+    /// The snake's health before the turn.
+    pub health: i32,
+
+    /// How many segments the snake grew by during the turn.
+    pub growth: u32,
+}
+
+impl TurnDelta {
+    /// Returns the IDs of all the snakes that were eliminated during the turn.
+    pub fn eliminated(&self) -> impl Iterator<Item = SnakeID> + '_ {
+        self.eliminated.iter().map(|snake| snake.id)
+    }
+
+    /// Returns the snakes that were eliminated during the turn, as they were when they were removed.
+    pub fn eliminated_snakes(&self) -> &[Snake] {
+        &self.eliminated
+    }
+
+    /// Returns how every snake changed during the turn.
+    pub fn moved(&self) -> &[MovedSnake] {
+        &self.moved
+    }
+
+    /// Returns the food that was eaten during the turn.
+    pub fn eaten(&self) -> &[Point] {
+        &self.eaten
+    }
+}
+
+/// Public API for simulating the game.
+impl GameState {
+    /// Advances the game by one turn, given a move for each snake. Snakes that are not given a move
+    /// keep moving in the direction they were last moving in. Returns the IDs of all the snakes that
+    /// were eliminated this turn.
+    pub fn advance(&mut self, moves: &HashMap<SnakeID, Move>) -> Vec<SnakeID> {
+        self.apply(moves).eliminated().collect()
+    }
+
+    /// Advances the game by one turn, just like `advance`, but returns everything that changed during
+    /// the turn so that it can be undone with `undo`.
+    pub fn apply(&mut self, moves: &HashMap<SnakeID, Move>) -> TurnDelta {
+        let hazard_damage = self.game.ruleset.settings.hazard_damage_per_turn as i32;
+        let squad =
+            (self.game.ruleset.name == GameType::Squad).then_some(self.game.ruleset.settings.squad);
+        let mut delta = TurnDelta {
+            moved: Vec::with_capacity(self.board.snakes.len()),
+            eaten: Vec::new(),
+            eliminated: Vec::new(),
+            hash: 0,
+        };
+
+        self.board.move_snakes(moves, &mut delta);
+        self.board.reduce_health();
+        self.board.damage_hazards(hazard_damage);
+        self.board.feed_snakes(&mut delta);
+        self.board.eliminate_snakes(
+            squad.is_some_and(|squad| squad.allow_body_collisions),
+            &mut delta,
+        );
+
+        if let Some(squad) = squad {
+            self.board.share_squads(squad, &mut delta);
+        }
+
+        if self.game.ruleset.name == GameType::Constrictor {
+            self.board.grow_snakes(&mut delta);
+        }
+
+        self.turn += 1;
+        self.update_you();
+
+        delta
+    }
+
+    /// Undoes a turn that was applied with `apply`, restoring the state to exactly what it was before.
+    /// Turns have to be undone in the reverse order they were applied in.
+    pub fn undo(&mut self, delta: TurnDelta) {
+        for snake in delta.eliminated {
+            self.board.snakes.insert(snake.id, snake);
+        }
+
+        self.board.food.extend(delta.eaten);
+
+        for moved in delta.moved {
+            let snake = self
+                .board
+                .snakes
+                .get_mut(&moved.id)
+                .expect("Every snake that moved should be back on the board.");
+
+            snake.retreat(moved.tail, moved.growth);
+            snake.health = moved.health;
+        }
+
+        self.turn -= 1;
+        self.update_you();
+    }
+
+    /// Returns true if the snake with the given ID is still on the board.
+    pub fn is_alive(&self, id: &SnakeID) -> bool {
+        self.board.snakes.contains_key(id)
+    }
+
+    /// Returns true if the game is over, i.e. there is at most one snake left on the board.
+    pub fn is_over(&self) -> bool {
+        self.board.snakes.len() <= 1
+    }
+
+    /// Returns all the moves the snake with the given ID can make without immediately running into a
+    /// wall or a body. See `Grid::safe_moves` for the details. If you need the safe moves of more than
+    /// one snake, build a `Grid` once and use that instead.
+    pub fn safe_moves(&self, id: &SnakeID) -> Vec<Move> {
+        let grid = Grid::new(&self.board);
+
+        grid.slot(id)
+            .map(|slot| grid.safe_moves(slot))
+            .unwrap_or_default()
+    }
+}
+
+/// Private API for simulating the game.
+impl GameState {
+    /// Keeps `you` in sync with the board. If we were eliminated, our health is set to 0 and the rest of
+    /// our last known state is kept around.
+    fn update_you(&mut self) {
+        match self.board.snakes.get(&self.you.id) {
+            Some(you) => self.you = you.clone(),
+            None => self.you.health = 0,
+        }
+    }
+}
+
+/// The individual stages of a turn, in the order the official ruleset runs them.
+impl Board {
+    /// Moves every snake one step in the direction of its move.
+    fn move_snakes(&mut self, moves: &HashMap<SnakeID, Move>, delta: &mut TurnDelta) {
+        let heads = self
+            .snakes
+            .values()
+            .map(|snake| {
+                let move_ = moves
+                    .get(&snake.id)
+                    .copied()
+                    .unwrap_or_else(|| self.default_move(snake));
+
+                (snake.id, self.move_point(&snake.head, move_))
+            })
+            .collect::<Vec<_>>();
+
+        for (id, head) in heads {
+            let snake = self.snakes.get_mut(&id).expect("The snake should exist.");
+
+            delta.moved.push(MovedSnake {
+                id,
+                tail: snake.tail(),
+                health: snake.health,
+                growth: 0,
+            });
+
+            snake.advance(head);
+        }
+    }
+
+    /// Every turn costs every snake one point of health.
+    fn reduce_health(&mut self) {
+        for snake in self.snakes.values_mut() {
+            snake.health -= 1;
+        }
+    }
+
+    /// Snakes whose heads are in a hazard take extra damage for every hazard stacked there, unless they are
+    /// also eating.
+    fn damage_hazards(&mut self, damage: i32) {
+        for snake in self.snakes.values_mut() {
+            let Some(&stack) = self.hazards.get(&snake.head) else {
+                continue;
+            };
+
+            if !self.food.contains(&snake.head) {
+                snake.health = (snake.health - damage * stack as i32).clamp(0, MAX_HEALTH);
+            }
+        }
+    }
+
+    /// Snakes whose heads are on food eat it, which resets their health and grows them by one. Every
+    /// snake on a piece of food gets to eat it (even if it just ran out of health), and then it is
+    /// removed from the board.
+    fn feed_snakes(&mut self, delta: &mut TurnDelta) {
+        for moved in delta.moved.iter_mut() {
+            let snake = self
+                .snakes
+                .get_mut(&moved.id)
+                .expect("Every snake that moved should be on the board.");
+
+            if self.food.contains(&snake.head) {
+                snake.health = MAX_HEALTH;
+                snake.grow();
+                moved.growth += 1;
+
+                if !delta.eaten.contains(&snake.head) {
+                    delta.eaten.push(snake.head);
+                }
+            }
+        }
+
+        for food in &delta.eaten {
+            self.food.remove(food);
+        }
+    }
+
+    /// Removes all the snakes that were eliminated this turn from the board. Snakes are first eliminated
+    /// for starving or leaving the board. Then, all the remaining snakes are checked against each other
+    /// at the same time for body and head-to-head collisions. Running into an ally's body only counts if
+    /// `allow_ally_collisions` is false.
+    fn eliminate_snakes(&mut self, allow_ally_collisions: bool, delta: &mut TurnDelta) {
+        let mut eliminated = self
+            .snakes
+            .values()
+            .filter(|snake| snake.health <= 0 || !self.is_on_board(&snake.head))
+            .map(|snake| snake.id)
+            .collect::<Vec<_>>();
+
+        let survivors = self
+            .snakes
+            .values()
+            .filter(|snake| !eliminated.contains(&snake.id))
+            .collect::<Vec<_>>();
+
+        let collided = survivors
+            .iter()
+            .filter(|snake| Self::has_collided(snake, &survivors, allow_ally_collisions))
+            .map(|snake| snake.id)
+            .collect::<Vec<_>>();
+
+        eliminated.extend(collided);
+
+        for id in &eliminated {
+            if let Some(snake) = self.snakes.remove(id) {
+                delta.eliminated.push(snake);
+            }
+        }
+    }
+
+    /// In constrictor games, every snake that survived the turn grows by one and gets its health back, so
+    /// snakes never get shorter and never starve.
+    fn grow_snakes(&mut self, delta: &mut TurnDelta) {
+        for moved in delta.moved.iter_mut() {
+            if let Some(snake) = self.snakes.get_mut(&moved.id) {
+                snake.health = MAX_HEALTH;
+                snake.grow();
+                moved.growth += 1;
+            }
+        }
+    }
+
+    /// Squadmates share their health and length: every surviving member of a squad gets the health and length
+    /// of the healthiest and longest member. With shared elimination, if any member of a squad was eliminated
+    /// this turn, the whole squad is.
+    fn share_squads(&mut self, squad: RulesetSquadSettings, delta: &mut TurnDelta) {
+        if squad.shared_elimination {
+            let fallen = delta
+                .eliminated
+                .iter()
+                .filter_map(|snake| snake.squad.clone())
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>();
+
+            let doomed = self
+                .snakes
+                .values()
+                .filter(|snake| {
+                    snake
+                        .squad
+                        .as_ref()
+                        .is_some_and(|name| fallen.contains(name))
+                })
+                .map(|snake| snake.id)
+                .collect::<Vec<_>>();
+
+            for id in doomed {
+                if let Some(snake) = self.snakes.remove(&id) {
+                    delta.eliminated.push(snake);
+                }
+            }
+        }
+
+        for moved in delta.moved.iter_mut() {
+            let Some(snake) = self.snakes.get(&moved.id) else {
+                continue;
+            };
+
+            let allies = self
+                .snakes
+                .values()
+                .filter(|other| other.is_ally_of(snake))
+                .collect::<Vec<_>>();
+
+            let health = allies
+                .iter()
+                .map(|ally| ally.health)
+                .fold(snake.health, i32::max);
+            let length = allies
+                .iter()
+                .map(|ally| ally.length)
+                .fold(snake.length, u32::max);
+
+            let snake = self
+                .snakes
+                .get_mut(&moved.id)
+                .expect("The snake should exist.");
+
+            if squad.shared_health {
+                snake.health = health;
+            }
+
+            if squad.shared_length {
+                while snake.length < length {
+                    snake.grow();
+                    moved.growth += 1;
+                }
+            }
+        }
+    }
+
+    /// Returns true if `snake` has run into any snake's body, or lost a head-to-head collision. A
+    /// snake loses a head-to-head if it is not strictly longer than the other snake. Running into an
+    /// ally's body only counts if `allow_ally_collisions` is false.
+    fn has_collided(snake: &Snake, snakes: &[&Snake], allow_ally_collisions: bool) -> bool {
+        snakes.iter().any(|other| {
+            snake.collides_with(other) && !(allow_ally_collisions && snake.is_ally_of(other))
+                || other.id != snake.id && other.head == snake.head && snake.length <= other.length
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::testing::{id, points, TestState};
+    use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    /// Turns a list of snake names and moves into the moves for a turn.
+    fn moves(moves: &[(char, Move)]) -> HashMap<SnakeID, Move> {
+        moves
+            .iter()
+            .map(|&(name, move_)| (id(name), move_))
+            .collect()
+    }
+
+    /// Gets the snake with the given name, which has to still be on the board.
+    fn snake(state: &GameState, name: char) -> &Snake {
+        &state.board.snakes[&id(name)]
+    }
+
+    #[test]
+    fn moving_drags_the_tail_along() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .snake('b', 90, &[(1, 1), (1, 2), (1, 3)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Up), ('b', Move::Right)]));
+
+        assert!(eliminated.is_empty());
+        assert_eq!(snake(&state, 'a').body, points(&[(5, 6), (5, 5), (5, 4)]));
+        assert_eq!(snake(&state, 'a').head, Point::new(5, 6));
+        assert_eq!(snake(&state, 'a').health, 89);
+        assert_eq!(snake(&state, 'a').length, 3);
+        assert_eq!(snake(&state, 'b').body, points(&[(2, 1), (1, 1), (1, 2)]));
+        assert_eq!(state.you, *snake(&state, 'a'));
+        assert_eq!(state.turn, 11);
+    }
+
+    #[test]
+    fn snakes_without_a_move_keep_going_straight() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (5, 4), (5, 3)])
+            .build();
+
+        state.advance(&HashMap::new());
+
+        assert_eq!(snake(&state, 'a').head, Point::new(5, 6));
+    }
+
+    #[test]
+    fn eating_restores_health_and_stacks_the_tail() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 30, &[(5, 5), (5, 4), (5, 3)])
+            .food(&[(5, 6), (0, 0)])
+            .build();
+
+        state.advance(&moves(&[('a', Move::Up)]));
+
+        assert_eq!(snake(&state, 'a').health, MAX_HEALTH);
+        assert_eq!(snake(&state, 'a').length, 4);
+        assert_eq!(
+            snake(&state, 'a').body,
+            points(&[(5, 6), (5, 5), (5, 4), (5, 4)])
+        );
+        assert_eq!(state.board.food, points(&[(0, 0)]).into_iter().collect());
+    }
+
+    #[test]
+    fn snakes_starve_without_health() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 1, &[(5, 5), (5, 4), (5, 3)])
+            .snake('b', 2, &[(1, 1), (1, 2), (1, 3)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Up), ('b', Move::Right)]));
+
+        assert_eq!(eliminated, vec![id('a')]);
+        assert!(!state.is_alive(&id('a')));
+        assert_eq!(snake(&state, 'b').health, 1);
+        assert_eq!(state.you.health, 0);
+    }
+
+    #[test]
+    fn eating_saves_a_starving_snake() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 1, &[(5, 5), (5, 4), (5, 3)])
+            .food(&[(5, 6)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Up)]));
+
+        assert!(eliminated.is_empty());
+        assert_eq!(snake(&state, 'a').health, MAX_HEALTH);
+    }
+
+    #[test]
+    fn snakes_die_leaving_the_board() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(0, 5), (1, 5), (2, 5)])
+            .snake('b', 90, &[(10, 5), (9, 5), (8, 5)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Left), ('b', Move::Right)]));
+
+        assert_eq!(eliminated.len(), 2);
+        assert!(state.board.snakes.is_empty());
+    }
+
+    #[test]
+    fn snakes_die_running_into_themselves() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (6, 5), (6, 4), (5, 4), (4, 4)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Down)]));
+
+        assert_eq!(eliminated, vec![id('a')]);
+    }
+
+    #[test]
+    fn snakes_can_follow_their_own_tail() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(5, 5), (6, 5), (6, 4), (5, 4)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Down)]));
+
+        assert!(eliminated.is_empty());
+        assert_eq!(
+            snake(&state, 'a').body,
+            points(&[(5, 4), (5, 5), (6, 5), (6, 4)])
+        );
+    }
+
+    #[test]
+    fn snakes_die_running_into_other_bodies() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(4, 5), (3, 5), (2, 5)])
+            .snake('b', 90, &[(5, 6), (5, 5), (5, 4), (5, 3)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Right), ('b', Move::Up)]));
+
+        assert_eq!(eliminated, vec![id('a')]);
+        assert!(state.is_alive(&id('b')));
+    }
+
+    #[test]
+    fn equal_head_to_heads_kill_both_snakes() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(4, 5), (3, 5), (2, 5)])
+            .snake('b', 90, &[(6, 5), (7, 5), (8, 5)])
+            .build();
+
+        let mut eliminated = state.advance(&moves(&[('a', Move::Right), ('b', Move::Left)]));
+        eliminated.sort_by_key(|id| id.to_string());
+
+        assert_eq!(eliminated, vec![id('a'), id('b')]);
+    }
+
+    #[test]
+    fn longer_snakes_win_head_to_heads() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 90, &[(4, 5), (3, 5), (2, 5)])
+            .snake('b', 90, &[(6, 5), (7, 5), (8, 5), (9, 5)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Right), ('b', Move::Left)]));
+
+        assert_eq!(eliminated, vec![id('a')]);
+        assert_eq!(snake(&state, 'b').head, Point::new(5, 5));
+    }
+
+    #[test]
+    fn stacked_hazards_deal_damage_for_every_hazard() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 80, &[(5, 5), (5, 4), (5, 3)])
+            .snake('b', 80, &[(1, 1), (1, 2), (1, 3)])
+            .hazards(&[(5, 6), (5, 6), (2, 1)])
+            .hazard_damage(14)
+            .build();
+
+        state.advance(&moves(&[('a', Move::Up), ('b', Move::Right)]));
+
+        assert_eq!(snake(&state, 'a').health, 80 - 1 - 2 * 14);
+        assert_eq!(snake(&state, 'b').health, 80 - 1 - 14);
+    }
+
+    #[test]
+    fn eating_in_a_hazard_cancels_its_damage() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 80, &[(5, 5), (5, 4), (5, 3)])
+            .hazards(&[(5, 6), (5, 6)])
+            .food(&[(5, 6)])
+            .build();
+
+        state.advance(&moves(&[('a', Move::Up)]));
+
+        assert_eq!(snake(&state, 'a').health, MAX_HEALTH);
+    }
+
+    #[test]
+    fn hazards_can_kill() {
+        let mut state = TestState::new(11, 11)
+            .snake('a', 20, &[(5, 5), (5, 4), (5, 3)])
+            .hazards(&[(5, 6)])
+            .hazard_damage(100)
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Up)]));
+
+        assert_eq!(eliminated, vec![id('a')]);
+    }
+
+    #[test]
+    fn moving_off_the_edge_of_a_wrapped_board_comes_back_on_the_other_side() {
+        let mut state = TestState::new(11, 11)
+            .game_type(GameType::Wrapped)
+            .snake('a', 90, &[(0, 5), (1, 5), (2, 5)])
+            .snake('b', 90, &[(5, 10), (5, 9), (5, 8)])
+            .build();
+
+        let eliminated = state.advance(&moves(&[('a', Move::Left), ('b', Move::Up)]));
+
+        assert!(eliminated.is_empty());
+        assert_eq!(snake(&state, 'a').body, points(&[(10, 5), (0, 5), (1, 5)]));
+        assert_eq!(snake(&state, 'b').body, points(&[(5, 0), (5, 10), (5, 9)]));
+    }
+
+    #[test]
+    fn constrictor_snakes_grow_and_heal_every_turn() {
+        // After the first turn of a constrictor game, every snake's tail is stacked, so tails stay where they are.
+        let mut state = TestState::new(11, 11)
+            .game_type(GameType::Constrictor)
+            .snake('a', 50, &[(5, 5), (5, 4), (5, 4)])
+            .snake('b', 50, &[(1, 1), (1, 2), (1, 2)])
+            .build();
+
+        for turn in 1..=3 {
+            let eliminated = state.advance(&moves(&[('a', Move::Up), ('b', Move::Right)]));
+
+            assert!(eliminated.is_empty());
+            assert_eq!(snake(&state, 'a').length, 3 + turn);
+            assert_eq!(snake(&state, 'a').health, MAX_HEALTH);
+            assert_eq!(snake(&state, 'a').body.last(), Some(&Point::new(5, 4)));
+            assert_eq!(snake(&state, 'b').body.last(), Some(&Point::new(1, 2)));
+            assert_eq!(snake(&state, 'b').length, 3 + turn);
+            assert_eq!(snake(&state, 'b').health, MAX_HEALTH);
+        }
+
+        assert_eq!(
+            snake(&state, 'a').body,
+            points(&[(5, 8), (5, 7), (5, 6), (5, 5), (5, 4), (5, 4)])
+        );
+    }
+
+    #[test]
+    fn constrictor_turns_can_be_undone() {
+        let initial = TestState::new(11, 11)
+            .game_type(GameType::Constrictor)
+            .snake('a', 50, &[(5, 5), (5, 4), (5, 4)])
+            .snake('b', 50, &[(1, 1), (1, 2), (1, 3)])
+            .food(&[(5, 6)])
+            .build();
+        let mut state = initial.clone();
+
+        let turns = [Move::Up, Move::Up, Move::Left]
+            .into_iter()
+            .map(|move_| state.apply(&moves(&[('a', move_), ('b', Move::Right)])))
+            .collect::<Vec<_>>();
+
+        assert_eq!(snake(&state, 'a').length, 3 + 3 + 1);
+
+        for delta in turns.into_iter().rev() {
+            state.undo(delta);
+        }
+
+        assert_eq!(state, initial);
+    }
+
+    /// A crowded board, where snakes eat, starve, collide and leave the board within a few turns. Snakes `a` and `b`
+    /// are on one squad, and `c` and `d` are on the other.
+    fn crowded(game_type: GameType, rng: &mut SmallRng) -> GameState {
+        TestState::new(7, 7)
+            .game_type(game_type)
+            .squad_rules(rng.gen(), rng.gen(), rng.gen(), rng.gen())
+            .squad_snake('a', "red", 12, &[(1, 1), (1, 2), (1, 3)])
+            .squad_snake('b', "red", 60, &[(5, 5), (5, 4), (5, 3), (5, 3)])
+            .squad_snake('c', "blue", 30, &[(3, 3), (3, 3), (3, 3)])
+            .squad_snake('d', "blue", 3, &[(1, 5), (2, 5), (3, 5), (4, 5), (4, 6)])
+            .food(&[(2, 2), (4, 4), (0, 0), (6, 6), (3, 1), (2, 4)])
+            .hazards(&[(0, 3), (1, 0), (1, 0)])
+            .build()
+    }
+
+    /// Picks a random move for every snake. Most of the time the move is safe, so games last a while, but
+    /// sometimes it's any move at all, so snakes also run into walls and bodies.
+    fn random_moves(state: &GameState, rng: &mut SmallRng) -> HashMap<SnakeID, Move> {
+        state
+            .board
+            .snakes
+            .keys()
+            .map(|id| {
+                let move_ = state
+                    .safe_moves(id)
+                    .choose(rng)
+                    .copied()
+                    .filter(|_| rng.gen_bool(0.8))
+                    .unwrap_or_else(|| Move::random(rng));
+                (*id, move_)
+            })
+            .collect()
+    }
+
+    /// Works out everything that happened during a turn, so that the test can check that the random games
+    /// really did cover every rule.
+    fn events(state: &GameState, delta: &TurnDelta) -> Vec<&'static str> {
+        let mut events = Vec::new();
+        let snakes = state
+            .board
+            .snakes
+            .values()
+            .chain(delta.eliminated_snakes())
+            .collect::<Vec<_>>();
+
+        if !delta.eaten().is_empty() {
+            events.push("eating");
+        }
+
+        for snake in delta.eliminated_snakes() {
+            let others = snakes.iter().filter(|other| other.id != snake.id);
+
+            if snake.health <= 0 {
+                events.push("starvation");
+            } else if !state.board.is_on_board(&snake.head) {
+                events.push("wall");
+            } else if others.clone().any(|other| other.head == snake.head) {
+                events.push("head-to-head");
+            } else if snakes
+                .iter()
+                .any(|other| other.body[1..].contains(&snake.head))
+            {
+                events.push("body");
+            }
+        }
+
+        for moved in delta.moved() {
+            let Some(snake) = state.board.snakes.get(&moved.id) else {
+                continue;
+            };
+            let ate = delta.eaten().contains(&snake.head) as u32;
+
+            match state.game.ruleset.name {
+                GameType::Constrictor if moved.growth > ate => events.push("constrictor growth"),
+                GameType::Squad if moved.growth > ate => events.push("squad length"),
+                GameType::Squad if snake.health != moved.health - 1 && ate == 0 => {
+                    events.push("squad health")
+                }
+                _ => {}
+            }
+        }
+
+        events
+    }
+
+    #[test]
+    fn undoing_turns_restores_the_state() {
+        let mut rng = SmallRng::seed_from_u64(8);
+        let mut seen = HashSet::new();
+
+        for game_type in GameType::ALL {
+            for _ in 0..200 {
+                let mut state = crowded(game_type, &mut rng);
+                let mut turns = Vec::new();
+
+                while !state.board.snakes.is_empty() && turns.len() < 15 {
+                    let before = state.clone();
+                    let delta = state.apply(&random_moves(&state, &mut rng));
+
+                    seen.extend(events(&state, &delta));
+                    turns.push((before, delta));
+                }
+
+                while let Some((before, delta)) = turns.pop() {
+                    state.undo(delta);
+
+                    assert_eq!(state, before, "{game_type:?}");
+                }
+            }
+        }
+
+        for event in [
+            "eating",
+            "starvation",
+            "wall",
+            "body",
+            "head-to-head",
+            "constrictor growth",
+            "squad length",
+            "squad health",
+        ] {
+            assert!(seen.contains(event), "No turn had any {event}.");
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use super::{moves::Move, point::Point};
+use super::point::Point;
 use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize};
 
 /// A battlesnake. I tried to make this as efficient to copy/manipulate as possible, but
@@ -41,10 +41,44 @@ impl Snake {
         *self.body.last().expect("All snakes should have a tail.")
     }
 
-    /// Gets the previous move of the snake.
-    pub fn previous_move(&self) -> Move {
-        Move::from_points(&self.body[1], &self.head)
-            .expect("Snake should have a valid previous move.")
+    /// Moves the snake's head to `head`, dragging its body along behind it. The tail is removed, so the
+    /// length of the snake stays the same.
+    pub fn advance(&mut self, head: Point) {
+        self.head = head;
+        self.body.insert(0, self.head);
+        self.body.pop();
+    }
+
+    /// Undoes `advance` (and `grow`, once for every segment of `growth`), by moving the snake's head back
+    /// and putting its old tail back on.
+    pub fn retreat(&mut self, tail: Point, growth: u32) {
+        for _ in 0..growth {
+            self.body.pop();
+            self.length -= 1;
+        }
+
+        self.body.remove(0);
+        self.body.push(tail);
+        self.head = self.body[0];
+    }
+
+    /// Grows the snake by one segment, by duplicating its tail.
+    pub fn grow(&mut self) {
+        self.body.push(self.tail());
+        self.length += 1;
+    }
+
+    /// Returns true if `other` is a different snake in the same squad as this snake. Snakes that aren't in a
+    /// squad (which the API sends as an empty squad) have no allies.
+    pub fn is_ally_of(&self, other: &Snake) -> bool {
+        self.id != other.id
+            && matches!((&self.squad, &other.squad), (Some(a), Some(b)) if !a.is_empty() && a == b)
+    }
+
+    /// Returns true if the snake's head has run into `other`'s body, not counting `other`'s head.
+    /// Passing the snake itself as `other` checks if it has run into itself.
+    pub fn collides_with(&self, other: &Snake) -> bool {
+        other.body[1..].contains(&self.head)
     }
 }
 
@@ -0,0 +1,172 @@
+// Helpers for building game states in tests. States are built as the JSON the API would send, and then parsed, so
+// they go through exactly the same code as real requests do. Snakes are named with a single character, which is
+// repeated to make up their ID.
+
+use super::{point::Point, settings::GameType, snake::SnakeID, GameState};
+use serde_json::{json, Value};
+
+/// A game state that is being built for a test.
+#[derive(Debug, Clone)]
+pub struct TestState {
+    /// The type of game being played.
+    game_type: GameType,
+
+    /// The width of the board.
+    width: u32,
+
+    /// The height of the board.
+    height: u32,
+
+    /// Every snake on the board, as JSON. The first snake is us.
+    snakes: Vec<Value>,
+
+    /// The food on the board.
+    food: Vec<Point>,
+
+    /// The hazards on the board. Stacked hazards are repeated.
+    hazards: Vec<Point>,
+
+    /// How much damage hazards deal.
+    hazard_damage: u32,
+
+    /// The squad settings, as JSON.
+    squad: Value,
+}
+
+impl TestState {
+    /// Starts building a standard game on a `width` by `height` board, with nothing on it.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            game_type: GameType::Standard,
+            width,
+            height,
+            snakes: Vec::new(),
+            food: Vec::new(),
+            hazards: Vec::new(),
+            hazard_damage: 14,
+            squad: json!({
+                "allowBodyCollisions": false,
+                "sharedElimination": false,
+                "sharedHealth": false,
+                "sharedLength": false,
+            }),
+        }
+    }
+
+    /// Sets the type of game being played.
+    pub fn game_type(mut self, game_type: GameType) -> Self {
+        self.game_type = game_type;
+        self
+    }
+
+    /// Adds a snake with the given name, health and body (from head to tail). The first snake added is us.
+    pub fn snake(self, name: char, health: i32, body: &[(i32, i32)]) -> Self {
+        self.squad_snake(name, "", health, body)
+    }
+
+    /// Adds a snake like `snake`, that is on the given squad.
+    pub fn squad_snake(
+        mut self,
+        name: char,
+        squad: &str,
+        health: i32,
+        body: &[(i32, i32)],
+    ) -> Self {
+        let body = points(body);
+
+        self.snakes.push(json!({
+            "id": id(name).to_string(),
+            "name": name.to_string(),
+            "health": health,
+            "body": body,
+            "head": body[0],
+            "length": body.len(),
+            "squad": squad,
+        }));
+        self
+    }
+
+    /// Adds food to the board.
+    pub fn food(mut self, food: &[(i32, i32)]) -> Self {
+        self.food.extend(points(food));
+        self
+    }
+
+    /// Adds hazards to the board. Points that are given more than once are stacked.
+    pub fn hazards(mut self, hazards: &[(i32, i32)]) -> Self {
+        self.hazards.extend(points(hazards));
+        self
+    }
+
+    /// Sets how much damage every hazard deals.
+    pub fn hazard_damage(mut self, damage: u32) -> Self {
+        self.hazard_damage = damage;
+        self
+    }
+
+    /// Sets the squad rules.
+    pub fn squad_rules(
+        mut self,
+        collisions: bool,
+        elimination: bool,
+        health: bool,
+        length: bool,
+    ) -> Self {
+        self.squad = json!({
+            "allowBodyCollisions": collisions,
+            "sharedElimination": elimination,
+            "sharedHealth": health,
+            "sharedLength": length,
+        });
+        self
+    }
+
+    /// Builds the game state.
+    pub fn build(self) -> GameState {
+        let you = self
+            .snakes
+            .first()
+            .cloned()
+            .expect("A game state needs at least one snake.");
+
+        serde_json::from_value(json!({
+            "game": {
+                "id": "2d3d6c2a-0b35-4bbd-9a0a-2f1c0d6c2e5b",
+                "ruleset": {
+                    "name": self.game_type.name(),
+                    "version": "v1.0.0",
+                    "settings": {
+                        "foodSpawnChance": 15,
+                        "minimumFood": 1,
+                        "hazardDamagePerTurn": self.hazard_damage,
+                        "royale": { "shrinkEveryNTurns": 25 },
+                        "squad": self.squad,
+                    },
+                },
+                "timeout": 500,
+                "source": "custom",
+            },
+            "turn": 10,
+            "board": {
+                "height": self.height,
+                "width": self.width,
+                "snakes": self.snakes,
+                "food": self.food,
+                "hazards": self.hazards,
+            },
+            "you": you,
+        }))
+        .expect("Test states should parse.")
+    }
+}
+
+/// The ID of the snake with the given name.
+pub fn id(name: char) -> SnakeID {
+    serde_json::from_value(json!(format!("gs_{}", name.to_string().repeat(24))))
+        .expect("Test snake IDs should parse.")
+}
+
+/// Turns a list of coordinates into points.
+pub fn points(points: &[(i32, i32)]) -> Vec<Point> {
+    points.iter().map(|&point| point.into()).collect()
+}
@@ -10,10 +10,14 @@
 // To get you started we've included code to prevent your Snake from moving backwards.
 // For more info see docs.battlesnake.com
 
-use crate::{configuration::Config, engine::Engine, objects::GameState};
-use log::{debug, info, warn};
+use crate::{
+    configuration::Config,
+    engine::{evaluation::Evaluator, strategy::StrategyRegistry, Engine},
+    objects::GameState,
+};
+use log::{debug, info, log_enabled, warn, Level};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use uuid::Uuid;
 
 /// The structure that handles all the `GET`/`POST` request logic for the game.
@@ -25,6 +29,12 @@ pub struct Server {
 
     /// A map of game IDs to `Engine` instances, which handle the game logic.
     games: HashMap<Uuid, Engine>,
+
+    /// The strategies every new engine picks from.
+    strategies: StrategyRegistry,
+
+    /// OPTIONAL: The evaluator every new engine's searches use, instead of the one from the engine configuration.
+    evaluator: Option<Arc<dyn Evaluator>>,
 }
 
 /// Public API for `Server`.
@@ -32,12 +42,26 @@ impl Server {
     /// Creates a new `Server` instance.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        Self::with_strategies(StrategyRegistry::default())
+    }
+
+    /// Creates a new `Server` instance, whose engines pick from the given strategies.
+    pub fn with_strategies(strategies: StrategyRegistry) -> Self {
         Self {
             config: Config::load(),
             games: HashMap::new(),
+            strategies,
+            evaluator: None,
         }
     }
 
+    /// Makes every new engine's searches judge unfinished games with the given evaluator, instead of the one
+    /// from the engine configuration.
+    pub fn with_evaluator(mut self, evaluator: impl Evaluator + 'static) -> Self {
+        self.evaluator = Some(Arc::new(evaluator));
+        self
+    }
+
     // info is called when you create your Snake on play.battlesnake.com
     // and controls your Snake's appearance
     // TIP: If you open your Snake URL in a browser you should see this data
@@ -85,7 +109,7 @@ impl Server {
 
         info!("Starting game '{id}'!");
 
-        let engine = Engine::new(self.config.engine, state.clone());
+        let engine = self.new_engine(state);
 
         self.games.insert(*id, engine);
     }
@@ -112,7 +136,7 @@ impl Server {
         } else {
             warn!("No engine found for game '{id}'!");
             warn!("Creating new engine...");
-            let engine = Engine::new(self.config.engine, state.clone());
+            let engine = self.new_engine(state);
             self.games.insert(*id, engine);
             self.games.get_mut(id).unwrap()
         };
@@ -120,8 +144,39 @@ impl Server {
         engine.update(state.clone());
         let chosen = engine.get_move();
 
-        info!("Moving {}", chosen);
+        // Explain what the engine thought of every move, so we can tell why it picked this one.
+        if log_enabled!(Level::Debug) {
+            for score in engine.score_moves() {
+                debug!("{score}");
+            }
+        }
+
+        info!("Moving {chosen} ({} strategy)", engine.strategy().name());
 
         json!({ "move": chosen })
     }
 }
+
+/// Private API for `Server`.
+impl Server {
+    /// Creates a new engine for the game, using the profile for the game's type and map.
+    fn new_engine(&self, state: &GameState) -> Engine {
+        let game = &state.game;
+        let config = self
+            .config
+            .engine_for(game.ruleset.name, game.map.as_deref());
+
+        debug!(
+            "Using the engine profile for {} games on the {} map",
+            game.ruleset.name.name(),
+            game.map.as_deref().unwrap_or("default")
+        );
+
+        let engine = Engine::with_strategies(config, state.clone(), self.strategies.clone());
+
+        match &self.evaluator {
+            Some(evaluator) => engine.with_evaluator(evaluator.clone()),
+            None => engine,
+        }
+    }
+}